@@ -1,15 +1,25 @@
+//! Benchmarks every day on its real input, embedded from `inputs/` when the benches are compiled.
+
 use aoc_2022::*;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 fn bench_day<const DAY: u8, S: Solution<DAY>>(solution: &S, input: &str, c: &mut Criterion) {
-    let mut group = c.benchmark_group(format!("Day {DAY:02}"));
-    group.noise_threshold(0.05);
-    group.bench_function("Part 1", |b| {
-        b.iter(|| solution.part1(black_box(&input.replace("\r\n", "\n"))))
-    });
-    group.bench_function("Part 2", |b| {
-        b.iter(|| solution.part2(black_box(&input.replace("\r\n", "\n"))))
+    // Named after every tag, e.g. `simulation/grid/Day 09`, so `cargo bench -- grid/` runs all
+    // the grid puzzles
+    let tags = puzzle::puzzle(DAY).map_or("untagged".to_string(), |puzzle| {
+        puzzle
+            .tags
+            .iter()
+            .copied()
+            .map(puzzle::Tag::name)
+            .collect::<Vec<_>>()
+            .join("/")
     });
+    let mut group = c.benchmark_group(format!("{tags}/Day {DAY:02}"));
+    group.noise_threshold(0.05);
+    let input = &*inputs::normalise(input);
+    group.bench_function("Part 1", |b| b.iter(|| solution.part1(black_box(input))));
+    group.bench_function("Part 2", |b| b.iter(|| solution.part2(black_box(input))));
     group.finish()
 }
 
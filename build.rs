@@ -0,0 +1,19 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    let out_dir = std::env::var("OUT_DIR").expect("cargo to set OUT_DIR");
+    let config = cbindgen::Config::from_file("cbindgen.toml").expect("cbindgen.toml to be valid");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()
+        .expect("src/ffi.rs to be parseable")
+        .write_to_file(std::path::Path::new(&out_dir).join("aoc_2022.h"));
+}
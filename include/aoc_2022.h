@@ -0,0 +1,47 @@
+#ifndef AOC_2022_H
+#define AOC_2022_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_NULL_POINTER = 1,
+  AOC_STATUS_INVALID_UTF8 = 2,
+  AOC_STATUS_UNKNOWN_DAY = 3,
+  AOC_STATUS_UNKNOWN_PART = 4,
+  AOC_STATUS_NOT_SOLVED = 5,
+  /**
+   * The solution panicked, usually because the input is malformed.
+   */
+  AOC_STATUS_PANICKED = 6,
+  /**
+   * The answer is longer than the buffer, `out_len` holds the length needed.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 7,
+} AocStatus;
+
+/**
+ * Solves one part of a day and copies the answer into `out` as UTF-8, without a terminating NUL.
+ *
+ * `*out_len` is the capacity of `out` on entry and the length of the answer on return. Nothing
+ * is written to `out` unless the result is `AOC_STATUS_OK`.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes, `out_len` to a writable `size_t` and `out` to
+ * `*out_len` writable bytes. Either pointer to bytes may be null if its length is zero.
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         uint8_t *out,
+                         size_t *out_len);
+
+#endif  /* AOC_2022_H */
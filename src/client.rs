@@ -0,0 +1,196 @@
+//! Talks to the puzzle website: downloads inputs into the inputs directory and submits answers.
+//!
+//! Requests are authenticated with the `session` cookie of a logged in browser and spaced at
+//! least [`Client::min_interval`] apart, tracked across runs in `<inputs>/.last-request`.
+
+use crate::ledger::Verdict;
+use crate::{inputs, Part};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+pub const DEFAULT_SESSION_FILE: &str = ".aoc-session";
+/// Takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/NotPaddy/AdventOfCode2022";
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Io(io::Error),
+    Status(u16, String),
+    Transport(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "No session token, set {SESSION_ENV} or write it to {DEFAULT_SESSION_FILE}"
+            ),
+            ClientError::Io(e) => write!(f, "{e}"),
+            ClientError::Status(status, body) => write!(f, "Server answered {status}: {body}"),
+            ClientError::Transport(e) => write!(f, "Request failed: {e}"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Reads the session token from [`SESSION_ENV`], falling back to `file`.
+///
+/// # Errors
+///
+/// Returns [`ClientError::MissingSession`] if neither holds a token.
+pub fn session_token(file: &Path) -> Result<String, ClientError> {
+    std::env::var(SESSION_ENV)
+        .or_else(|_| fs::read_to_string(file))
+        .map(|token| token.trim().to_string())
+        .ok()
+        .filter(|token| !token.is_empty())
+        .ok_or(ClientError::MissingSession)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    inputs: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, inputs: &Path) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            inputs: inputs.to_path_buf(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    #[must_use]
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Downloads the input for `day` unless the inputs directory already has it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the download fails or the input can't be written.
+    pub fn fetch(&self, day: u8) -> Result<Fetched, ClientError> {
+        let path = inputs::path(&self.inputs, day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let input = self.get(&format!("/day/{day}/input"))?;
+        // Write next to the target first so an interrupted download isn't mistaken for an input
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, &path)?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Posts `answer` for one part of `day` and reads the verdict from the response page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Verdict, ClientError> {
+        self.throttle()?;
+        let level = part.to_string();
+        let response = ureq::post(&format!("{}/day/{day}/answer", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &level), ("answer", answer)]);
+        Ok(verdict(&Self::read_body(response)?))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        self.throttle()?;
+        let response = ureq::get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        Self::read_body(response)
+    }
+
+    fn read_body(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => Err(ClientError::Status(
+                status,
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(e) => Err(ClientError::Transport(e.to_string())),
+        }
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request, then records this one.
+    fn throttle(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.inputs)?;
+        let stamp = self.inputs.join(LAST_REQUEST_FILE);
+        let now = SystemTime::now();
+
+        let last_request = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        let remaining = last_request
+            .map(|last| now.duration_since(last).unwrap_or_default())
+            .and_then(|elapsed| self.min_interval.checked_sub(elapsed));
+        if let Some(remaining) = remaining {
+            thread::sleep(remaining);
+        }
+
+        // Rounded up, so the next request can't be let through up to a millisecond early
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .div_ceil(1_000_000);
+        fs::write(stamp, millis.to_string())
+    }
+}
+
+fn verdict(page: &str) -> Verdict {
+    if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if page.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else if page.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else {
+        Verdict::Unknown
+    }
+}
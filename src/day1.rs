@@ -1,28 +1,162 @@
 use crate::Solution;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::{IntErrorKind, ParseIntError};
 
 pub struct Day1;
 
 impl Solution<1> for Day1 {
-    type Output = u32;
+    type Output = u64;
 
     fn part1(&self, input: &str) -> Self::Output {
-        Self::get_elf_calories(input).max().unwrap()
+        Self::totals(input).max().unwrap()
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
-        let mut elves: Vec<u32> = Self::get_elf_calories(input).collect();
-        elves.sort_unstable();
+        Some(top_k(Self::totals(input), 3).iter().sum())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Parsing {
+    /// Fail on the first line that is not a number of calories.
+    #[default]
+    Strict,
+    /// Count lines that are not a number of calories as 0.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub elf: usize,
+    /// Line number in the input, starting at 1.
+    pub line: usize,
+    pub text: String,
+    source: ParseIntError,
+}
+
+impl ParseError {
+    /// Whether the line is a number, but too large for the calories of an item.
+    #[must_use]
+    pub fn is_overflow(&self) -> bool {
+        *self.source.kind() == IntErrorKind::PosOverflow
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} (elf {}): invalid calories {:?}: {}",
+            self.line, self.elf, self.text, self.source
+        )
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Elf {
+    /// Position of the elf in the input, starting at 0.
+    pub index: usize,
+    pub items: Vec<u32>,
+    pub total: u64,
+}
+
+/// Every elf of the input, in order. Each blank line ends an elf.
+pub fn elves(input: &str, parsing: Parsing) -> impl Iterator<Item = Result<Elf, ParseError>> + '_ {
+    let mut lines = input.lines().enumerate();
+    let mut index = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let mut items = Vec::new();
+        loop {
+            let Some((number, line)) = lines.next() else {
+                done = true;
+                break;
+            };
+            if line.is_empty() {
+                break;
+            }
+            match (line.parse::<u32>(), parsing) {
+                (Ok(calories), _) => items.push(calories),
+                (Err(_), Parsing::Lenient) => items.push(0),
+                (Err(source), Parsing::Strict) => {
+                    done = true;
+                    return Some(Err(ParseError {
+                        elf: index,
+                        line: number + 1,
+                        text: line.to_string(),
+                        source,
+                    }));
+                }
+            }
+        }
+        index += 1;
+        Some(Ok(Elf {
+            index: index - 1,
+            total: items.iter().copied().map(u64::from).sum(),
+            items,
+        }))
+    })
+}
+
+/// The `k` elves carrying the most calories, most first. Ties go to the elf listed first.
+///
+/// # Errors
+///
+/// Returns the first malformed line when parsing strictly.
+pub fn top_elves(input: &str, k: usize, parsing: Parsing) -> Result<Vec<Elf>, ParseError> {
+    let mut error = Ok(());
+    let elves = elves(input, parsing).scan(&mut error, |error, elf| {
+        elf.map_err(|e| **error = Err(e)).ok()
+    });
+    let top = top_k(
+        elves.map(|elf| (elf.total, Reverse(elf.index), elf.items)),
+        k,
+    );
+    error?;
+    Ok(top
+        .into_iter()
+        .map(|(total, Reverse(index), items)| Elf {
+            index,
+            items,
+            total,
+        })
+        .collect())
+}
 
-        let sum = elves.iter().rev().take(3).sum();
-        Some(sum)
+/// The `k` largest values in descending order, keeping only `k` of them around at any time.
+fn top_k<T: Ord>(values: impl Iterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
     }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for value in values {
+        heap.push(Reverse(value));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    // Ascending order of `Reverse` is descending order of the values
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(value)| value)
+        .collect()
 }
 
 impl Day1 {
-    fn get_elf_calories(input: &str) -> impl Iterator<Item = u32> + '_ {
-        input
-            .split("\n\n")
-            .map(|elf| elf.lines().map(|l| l.parse::<u32>().unwrap_or(0)).sum())
+    fn totals(input: &str) -> impl Iterator<Item = u64> + '_ {
+        elves(input, Parsing::Strict).map(|elf| elf.unwrap_or_else(|e| panic!("{e}")).total)
     }
 }
 
@@ -57,4 +191,71 @@ mod test {
     fn test_part2() {
         assert_eq!(Day1.part2(TEST_INPUT), Some(45000));
     }
+
+    #[test]
+    fn test_top_elves() {
+        let top = top_elves(TEST_INPUT, 2, Parsing::Strict).unwrap();
+        assert_eq!(
+            top,
+            [
+                Elf {
+                    index: 3,
+                    items: vec![7000, 8000, 9000],
+                    total: 24000
+                },
+                Elf {
+                    index: 2,
+                    items: vec![5000, 6000],
+                    total: 11000
+                },
+            ]
+        );
+        assert!(top_elves(TEST_INPUT, 0, Parsing::Strict)
+            .unwrap()
+            .is_empty());
+        assert_eq!(top_elves(TEST_INPUT, 10, Parsing::Strict).unwrap().len(), 5);
+
+        let ties = top_elves("5\n\n3\n2\n\n1", 2, Parsing::Strict).unwrap();
+        assert_eq!((ties[0].index, ties[1].index), (0, 1));
+    }
+
+    #[test]
+    fn test_elves() {
+        let items = elves("1\n2\n\n3\n\n", Parsing::Strict)
+            .map(|elf| elf.unwrap().items)
+            .collect::<Vec<_>>();
+        assert_eq!(items, [vec![1, 2], vec![3], vec![]]);
+        assert_eq!(elves("", Parsing::Strict).count(), 1);
+        let crlf = elves("1\r\n2\r\n\r\n3\r\n", Parsing::Strict)
+            .map(|elf| elf.unwrap().total)
+            .collect::<Vec<_>>();
+        assert_eq!(crlf, [3, 3]);
+
+        let huge = "4000000000\n4000000000\n";
+        assert_eq!(Day1.part1(huge), 8_000_000_000);
+    }
+
+    #[test]
+    fn test_strict_parsing() {
+        let input = "1000\n\n2000\n20x0\n\n3000\n";
+        let error = top_elves(input, 1, Parsing::Strict).unwrap_err();
+        assert_eq!((error.elf, error.line, error.text.as_str()), (1, 4, "20x0"));
+        assert!(!error.is_overflow());
+        assert_eq!(
+            error.to_string(),
+            "line 4 (elf 1): invalid calories \"20x0\": invalid digit found in string"
+        );
+
+        let top = top_elves(input, 2, Parsing::Lenient).unwrap();
+        assert_eq!((top[1].index, &top[1].items[..]), (1, &[2000, 0][..]));
+
+        let error = elves("1\n\n99999999999\n", Parsing::Strict)
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!((error.elf, error.line), (1, 3));
+        assert!(error.is_overflow());
+
+        let error = crate::registry::solve(1, crate::Part::One, "abc\n").unwrap_err();
+        assert!(error.to_string().contains("line 1 (elf 0)"));
+    }
 }
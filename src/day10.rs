@@ -4,6 +4,7 @@ use std::fmt::{Display, Formatter};
 
 pub struct Day10;
 
+#[allow(clippy::cast_possible_wrap)]
 impl Solution<10> for Day10 {
     type Output = DayResult;
 
@@ -1,5 +1,6 @@
 use crate::Solution;
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Day11;
@@ -23,7 +24,7 @@ impl Solution<11> for Day11 {
 }
 
 fn get_monkey_business<F: Fn(u64) -> u64>(
-    monkeys: &mut Vec<Monkey>,
+    monkeys: &mut [Monkey],
     rounds: usize,
     worry_fn: F,
 ) -> u64 {
@@ -41,7 +42,7 @@ fn get_monkey_business<F: Fn(u64) -> u64>(
             while !monkeys[i].items.is_empty() {
                 let item = monkeys[i].items.pop_front().unwrap();
                 let item = worry_fn(operation.invoke(item));
-                let target = if item % test_divisor == 0 {
+                let target = if item.is_multiple_of(test_divisor) {
                     target_true
                 } else {
                     target_false
@@ -55,7 +56,13 @@ fn get_monkey_business<F: Fn(u64) -> u64>(
     inspections.iter().take(2).product()
 }
 
-struct Monkey {
+/// Parses every monkey description of the input, in order.
+#[must_use]
+pub fn monkeys(input: &str) -> Vec<Monkey> {
+    Monkey::parse_all(input).collect()
+}
+
+pub struct Monkey {
     items: VecDeque<u64>,
     operation: Operation,
     test_divisor: u64,
@@ -74,9 +81,7 @@ impl FromStr for Monkey {
                 .split(',')
                 .filter_map(|i| i.trim().parse().ok())
                 .collect(),
-            operation: Self::next_strip_prefix(iter, "Operation: ")?
-                .parse()
-                .map_err(|_| ())?,
+            operation: Self::next_strip_prefix(iter, "Operation: ")?.parse()?,
             test_divisor: Self::next_strip_prefix(iter, "Test: divisible by ")?
                 .trim()
                 .parse()
@@ -94,6 +99,27 @@ impl FromStr for Monkey {
 }
 
 impl Monkey {
+    #[must_use]
+    pub fn items(&self) -> &VecDeque<u64> {
+        &self.items
+    }
+
+    #[must_use]
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    #[must_use]
+    pub fn test_divisor(&self) -> u64 {
+        self.test_divisor
+    }
+
+    /// The monkeys an item is thrown to if the test passes and if it fails.
+    #[must_use]
+    pub fn targets(&self) -> (usize, usize) {
+        (self.target_true, self.target_false)
+    }
+
     fn parse_all(input: &str) -> impl Iterator<Item = Monkey> + '_ {
         input.split("\n\n").filter_map(|m| m.parse::<Monkey>().ok())
     }
@@ -110,15 +136,16 @@ impl Monkey {
     }
 }
 
-#[derive(Copy, Clone)]
-enum Operation {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operation {
     Square,
     Multiply(u64),
     Add(u64),
 }
 
 impl Operation {
-    fn invoke(&self, value: u64) -> u64 {
+    #[must_use]
+    pub fn invoke(&self, value: u64) -> u64 {
         match self {
             Operation::Square => value * value,
             Operation::Multiply(arg) => value * arg,
@@ -127,6 +154,16 @@ impl Operation {
     }
 }
 
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Square => write!(f, "old * old"),
+            Operation::Multiply(arg) => write!(f, "old * {arg}"),
+            Operation::Add(arg) => write!(f, "old + {arg}"),
+        }
+    }
+}
+
 impl FromStr for Operation {
     type Err = ();
 
@@ -185,4 +222,15 @@ mod test {
     fn test_part2() {
         assert_eq!(Day11.part2(TEST_INPUT), Some(2_713_310_158));
     }
+
+    #[test]
+    fn test_monkeys() {
+        let monkeys = monkeys(TEST_INPUT);
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(monkeys[1].items(), &[54, 65, 75, 74]);
+        assert_eq!(monkeys[2].operation().to_string(), "old * old");
+        assert_eq!(monkeys[2].operation().invoke(3), 9);
+        assert_eq!(monkeys[3].test_divisor(), 17);
+        assert_eq!(monkeys[3].targets(), (0, 1));
+    }
 }
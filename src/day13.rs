@@ -1,3 +1,4 @@
+use crate::iter_ext::ArrayChunkedExt;
 use crate::Solution;
 use std::cmp::Ordering;
 use std::slice;
@@ -13,7 +14,7 @@ impl Solution<13> for Day13 {
             .lines()
             .filter(|l| !l.is_empty())
             .map(|l| l.parse::<Packet>().expect("Packet can be parsed"))
-            .array_chunks::<2>()
+            .array_chunked::<2>()
             .enumerate()
             .filter(|(_, [a, b])| a <= b)
             .map(|(idx, _)| idx + 1)
@@ -21,7 +22,7 @@ impl Solution<13> for Day13 {
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
-        let dividers = vec![
+        let dividers = [
             "[[2]]".parse::<Packet>().expect("Divider must be valid"),
             "[[6]]".parse::<Packet>().expect("Divider must be valid"),
         ];
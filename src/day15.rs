@@ -10,25 +10,76 @@ pub struct Day15 {
 
 impl Day15 {
     #[must_use]
-    pub fn puzzle() -> Day15 {
+    pub const fn puzzle() -> Day15 {
         Self {
             target_row: 2_000_000,
             max_coordinate: 4_000_000,
         }
     }
+
+    #[must_use]
+    pub const fn new(target_row: i32, max_coordinate: i32) -> Day15 {
+        Self {
+            target_row,
+            max_coordinate,
+        }
+    }
+
+    #[must_use]
+    pub const fn target_row(&self) -> i32 {
+        self.target_row
+    }
+
+    #[must_use]
+    pub const fn max_coordinate(&self) -> i32 {
+        self.max_coordinate
+    }
 }
 
-#[allow(clippy::cast_sign_loss)]
 impl Solution<15> for Day15 {
     type Output = u64;
 
     fn part1(&self, input: &str) -> Self::Output {
-        let sensors = get_sensors_with_distance(input);
+        Sensors::parse(input).covered_in_row(self.target_row)
+    }
+
+    fn part2(&self, input: &str) -> Option<Self::Output> {
+        Sensors::parse(input).tuning_frequency(self.max_coordinate)
+    }
+}
+
+/// The parsed sensors of an input with the distance to their closest beacon.
+pub struct Sensors {
+    sensors: Vec<(Sensor, i32)>,
+}
 
+#[allow(clippy::cast_sign_loss)]
+impl Sensors {
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
+        Self {
+            sensors: get_sensors_with_distance(input),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sensors.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty()
+    }
+
+    /// Number of positions in `row` that can't hold a beacon.
+    #[must_use]
+    pub fn covered_in_row(&self, row: i32) -> u64 {
+        let sensors = &self.sensors;
         let mut spans: Vec<(i32, i32)> = vec![(i32::MIN, i32::MAX); sensors.len()];
         let mut positions = 0;
         let mut max_end = i32::MIN;
-        for &(start, end) in process_span_gaps(self.target_row, &mut spans, &sensors) {
+        for &(start, end) in process_span_gaps(row, &mut spans, sensors) {
             positions += (end - max(max_end, start)).max(0) as u32;
             max_end = end.max(max_end);
         }
@@ -36,9 +87,10 @@ impl Solution<15> for Day15 {
         u64::from(positions)
     }
 
-    fn part2(&self, input: &str) -> Option<Self::Output> {
-        let sensors = get_sensors_with_distance(input);
-
+    /// Tuning frequency of the only position within `0..=max_coordinate` that no sensor covers.
+    #[must_use]
+    pub fn tuning_frequency(&self, max_coordinate: i32) -> Option<u64> {
+        let sensors = &self.sensors;
         let corners = sensors
             .iter()
             .map(|(sensor, distance)| (sensor.position.rotate(), distance))
@@ -74,7 +126,7 @@ impl Solution<15> for Day15 {
             .cartesian_product(y_candidates)
             .map(|(x, y)| Coordinate { x, y }.rotate_back())
             .filter(|coordinate| {
-                let range = 0..=self.max_coordinate;
+                let range = 0..=max_coordinate;
                 range.contains(&coordinate.x) && range.contains(&coordinate.y)
             })
             .filter(|coordinate| {
@@ -122,7 +174,7 @@ fn process_span_gaps<'a>(
         }
     }
 
-    spans[..length].sort_by(|(a, _), (b, _)| a.cmp(b));
+    spans[..length].sort_by_key(|&(start, _)| start);
     &spans[..length]
 }
 
@@ -141,7 +193,7 @@ impl Coordinate {
     }
 
     fn rotate_back(&self) -> Self {
-        let y = (self.x + self.y) / 2;
+        let y = i32::midpoint(self.x, self.y);
         Self { x: self.y - y, y }
     }
 
@@ -245,4 +297,12 @@ mod test {
     fn test_part2() {
         assert_eq!(Day15::test().part2(TEST_INPUT), Some(56_000_011));
     }
+
+    #[test]
+    fn test_sensors() {
+        let sensors = Sensors::parse(TEST_INPUT);
+        assert_eq!(sensors.len(), 14);
+        assert_eq!(sensors.covered_in_row(10), 26);
+        assert_eq!(sensors.tuning_frequency(20), Some(56_000_011));
+    }
 }
@@ -12,38 +12,70 @@ impl Solution<16> for Day16 {
     type Output = u32;
 
     fn part1(&self, input: &str) -> Self::Output {
-        let mut valves = input
-            .lines()
-            .map(|l| l.parse::<Valve>().expect("Line to be parseable"))
-            .collect::<Vec<_>>();
-
-        let (start_index, flow_valves, flow_state) = optimize_flow(&mut valves, "AA");
-
-        flow_state[(MINUTES - 1, start_index, flow_valves - 1)]
+        FlowState::parse(input).max_release(MINUTES).unwrap()
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
+        FlowState::parse(input).max_release_with_elephant(MINUTES - 4)
+    }
+}
+
+/// The most pressure that can be released from `AA` for every time budget, position and set of
+/// valves allowed to be opened.
+pub struct FlowState {
+    start_index: usize,
+    flow_valves: usize,
+    releases: Array3<u32>,
+}
+
+impl FlowState {
+    /// # Panics
+    ///
+    /// Panics if a line is not a valve description.
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
         let mut valves = input
             .lines()
             .map(|l| l.parse::<Valve>().expect("Line to be parseable"))
             .collect::<Vec<_>>();
 
         let (start_index, flow_valves, flow_state) = optimize_flow(&mut valves, "AA");
+        Self {
+            start_index,
+            flow_valves,
+            releases: flow_state,
+        }
+    }
+
+    /// Returns `None` unless `minutes` is between 1 and 30.
+    #[must_use]
+    pub fn max_release(&self, minutes: usize) -> Option<u32> {
+        let step = Self::step(minutes)?;
+        Some(self.releases[(step, self.start_index, self.flow_valves - 1)])
+    }
 
+    /// Like [`FlowState::max_release`], with an elephant opening a disjoint set of valves.
+    #[must_use]
+    pub fn max_release_with_elephant(&self, minutes: usize) -> Option<u32> {
+        let step = Self::step(minutes)?;
         let mut max_flow = 0;
-        for valve_a in 0..flow_valves / 2 {
-            let valve_b = flow_valves - 1 - valve_a;
-            let own_flow = flow_state[(MINUTES - 5, start_index, valve_a)];
-            let elephant_flow = flow_state[(MINUTES - 5, start_index, valve_b)];
+        for valve_a in 0..self.flow_valves / 2 {
+            let valve_b = self.flow_valves - 1 - valve_a;
+            let own_flow = self.releases[(step, self.start_index, valve_a)];
+            let elephant_flow = self.releases[(step, self.start_index, valve_b)];
             max_flow = max(max_flow, own_flow + elephant_flow);
         }
 
         Some(max_flow)
     }
+
+    fn step(minutes: usize) -> Option<usize> {
+        (1..=MINUTES).contains(&minutes).then(|| minutes - 1)
+    }
 }
 
 #[allow(clippy::cast_possible_truncation)]
-fn optimize_flow(valves: &mut Vec<Valve>, start_name: &str) -> (usize, usize, Array3<u32>) {
+fn optimize_flow(valves: &mut [Valve], start_name: &str) -> (usize, usize, Array3<u32>) {
     valves.sort_by_key(|v| Reverse(v.flow_rate));
     let valve_indices = valves
         .iter()
@@ -189,4 +221,13 @@ mod test {
     fn test_part2() {
         assert_eq!(Day16.part2(TEST_INPUT), Some(1707));
     }
+
+    #[test]
+    fn test_flow_state() {
+        let flow_state = FlowState::parse(TEST_INPUT);
+        assert_eq!(flow_state.max_release(30), Some(1651));
+        assert_eq!(flow_state.max_release(26), Some(1327));
+        assert_eq!(flow_state.max_release(31), None);
+        assert_eq!(flow_state.max_release_with_elephant(26), Some(1707));
+    }
 }
@@ -10,6 +10,7 @@ use nom::sequence::{delimited, terminated, tuple};
 use nom::sequence::{preceded, separated_pair};
 use nom::IResult;
 use nom::{AsChar, InputTakeAtPosition, Parser};
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
 use std::array;
 use std::cmp::max;
@@ -20,25 +21,41 @@ impl Solution<19> for Day19 {
     type Output = u32;
 
     fn part1(&self, input: &str) -> Self::Output {
-        iterator(input, terminated(parse_blueprint, multispace0))
-            .collect::<Vec<_>>()
-            .par_iter()
-            .map(|bp| bp.quality_level(24))
+        let blueprints =
+            iterator(input, terminated(parse_blueprint, multispace0)).collect::<Vec<_>>();
+        map_blueprints(&blueprints, |bp| bp.quality_level(24))
+            .iter()
             .sum()
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
+        let blueprints = iterator(input, terminated(parse_blueprint, multispace0))
+            .take(3)
+            .collect::<Vec<_>>();
         Some(
-            iterator(input, terminated(parse_blueprint, multispace0))
-                .take(3)
-                .collect::<Vec<_>>()
-                .par_iter()
-                .map(|bp| bp.maximum_geode_count(32))
+            map_blueprints(&blueprints, |bp| bp.maximum_geode_count(32))
+                .iter()
                 .product(),
         )
     }
 }
 
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn map_blueprints<F>(blueprints: &[Blueprint], f: F) -> Vec<u32>
+where
+    F: Fn(&Blueprint) -> u32 + Sync + Send,
+{
+    blueprints.par_iter().map(f).collect()
+}
+
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn map_blueprints<F>(blueprints: &[Blueprint], f: F) -> Vec<u32>
+where
+    F: Fn(&Blueprint) -> u32,
+{
+    blueprints.iter().map(f).collect()
+}
+
 #[derive(Debug)]
 struct Blueprint {
     id: u8,
@@ -63,9 +80,8 @@ impl State {
                 } else if self.robots[material] == 0 {
                     max_time + 1
                 } else {
-                    (material_costs[material] - self.inventory[material] + self.robots[material]
-                        - 1)
-                        / self.robots[material]
+                    (material_costs[material] - self.inventory[material])
+                        .div_ceil(self.robots[material])
                 }
             })
             .max()
@@ -231,7 +247,6 @@ fn parse_geode_bot(input: &str) -> IResult<&str, RobotCost> {
 mod test {
     use super::*;
     use indoc::indoc;
-    use std::assert_matches::assert_matches;
 
     const TEST_INPUT: &str = indoc! {"
         Blueprint 1:
@@ -249,10 +264,7 @@ mod test {
 
     #[test]
     fn test_parse() {
-        assert_matches!(
-            parse_blueprint(TEST_INPUT.split_once("\n\n").unwrap().0),
-            Ok(_)
-        );
+        assert!(parse_blueprint(TEST_INPUT.split_once("\n\n").unwrap().0).is_ok());
     }
 
     #[test]
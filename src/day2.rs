@@ -6,38 +6,352 @@ impl Solution<2> for Day2 {
     type Output = u32;
 
     fn part1(&self, input: &str) -> Self::Output {
-        input
-            .lines()
-            .filter_map(parse_line)
-            .map(|(opp, own)| u32::from(score(opp, own)))
-            .sum()
+        Game::rock_paper_scissors().total_score(input, Decoding::Shape)
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
-        let score = input
-            .lines()
-            .filter_map(parse_line)
-            .map(|(opp, outcome)| (opp, get_own(opp, outcome)))
-            .map(|(opp, own)| u32::from(score(opp, own)))
-            .sum();
-        Some(score)
+        Some(Game::rock_paper_scissors().total_score(input, Decoding::Outcome))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Scoring {
+    pub loss: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl Scoring {
+    pub const STANDARD: Scoring = Scoring {
+        loss: 0,
+        draw: 3,
+        win: 6,
+    };
+
+    #[must_use]
+    pub fn score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
     }
 }
 
-fn parse_line(line: &str) -> Option<(u8, u8)> {
-    let bytes = line.as_bytes();
-    let opp = bytes.first()? - b'A' + 1;
-    let own = bytes.get(2)? - b'X' + 1;
-    Some((opp, own))
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Shape {
+    pub name: &'static str,
+    /// Letter of the shape in the first column of the guide.
+    pub opponent: char,
+    /// Letter of the shape in the second column of the guide.
+    pub column: char,
+    pub score: u32,
+}
+
+/// How to read the second column of the guide.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Decoding {
+    /// The shape to play.
+    Shape,
+    /// The outcome to reach: the column letters of the first three shapes stand for a loss, a
+    /// draw and a win. Of several shapes reaching it, the first one is played.
+    Outcome,
+}
+
+/// A variant of rock paper scissors: its shapes, which shape beats which and what outcomes are
+/// worth.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Game {
+    shapes: Vec<Shape>,
+    /// Bitmask of the shapes each shape beats.
+    beats: Vec<u64>,
+    scoring: Scoring,
+}
+
+impl Game {
+    /// # Errors
+    ///
+    /// Returns an error if a letter is used twice or the beats relation names an unknown shape,
+    /// has a shape beating itself or two shapes beating each other.
+    pub fn new(
+        shapes: Vec<Shape>,
+        beats: &[(&str, &str)],
+        scoring: Scoring,
+    ) -> Result<Self, String> {
+        if shapes.len() > 64 {
+            return Err(format!("Too many shapes: {}", shapes.len()));
+        }
+        for (i, shape) in shapes.iter().enumerate() {
+            if let Some(other) = shapes[..i]
+                .iter()
+                .find(|o| o.opponent == shape.opponent || o.column == shape.column)
+            {
+                return Err(format!("{} and {} share a letter", other.name, shape.name));
+            }
+        }
+
+        let index = |name: &str| {
+            shapes
+                .iter()
+                .position(|shape| shape.name == name)
+                .ok_or_else(|| format!("Unknown shape {name}"))
+        };
+        let mut relation = vec![0u64; shapes.len()];
+        for &(winner, loser) in beats {
+            let (winner_index, loser_index) = (index(winner)?, index(loser)?);
+            if winner_index == loser_index {
+                return Err(format!("{winner} beats itself"));
+            }
+            if relation[loser_index] & (1 << winner_index) != 0 {
+                return Err(format!("{winner} and {loser} beat each other"));
+            }
+            relation[winner_index] |= 1 << loser_index;
+        }
+
+        Ok(Self {
+            shapes,
+            beats: relation,
+            scoring,
+        })
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // The presets are valid
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(
+            vec![
+                Self::shape("Rock", 'A', 'X', 1),
+                Self::shape("Paper", 'B', 'Y', 2),
+                Self::shape("Scissors", 'C', 'Z', 3),
+            ],
+            &[
+                ("Rock", "Scissors"),
+                ("Paper", "Rock"),
+                ("Scissors", "Paper"),
+            ],
+            Scoring::STANDARD,
+        )
+        .unwrap()
+    }
+
+    /// Lizard and Spock extend the guide with the letters D/W and E/V.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(
+            vec![
+                Self::shape("Rock", 'A', 'X', 1),
+                Self::shape("Paper", 'B', 'Y', 2),
+                Self::shape("Scissors", 'C', 'Z', 3),
+                Self::shape("Lizard", 'D', 'W', 4),
+                Self::shape("Spock", 'E', 'V', 5),
+            ],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+            Scoring::STANDARD,
+        )
+        .unwrap()
+    }
+
+    fn shape(name: &'static str, opponent: char, column: char, score: u32) -> Shape {
+        Shape {
+            name,
+            opponent,
+            column,
+            score,
+        }
+    }
+
+    #[must_use]
+    pub fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    #[must_use]
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Outcome of playing `own` against `opp`, both indices into [`Game::shapes`].
+    #[must_use]
+    pub fn outcome(&self, opp: usize, own: usize) -> Outcome {
+        if self.beats[own] & (1 << opp) != 0 {
+            Outcome::Win
+        } else if self.beats[opp] & (1 << own) != 0 {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    #[must_use]
+    pub fn score(&self, opp: usize, own: usize) -> u32 {
+        self.shapes[own].score + self.scoring.score(self.outcome(opp, own))
+    }
+
+    /// The first shape that reaches `outcome` against `opp`.
+    #[must_use]
+    pub fn shape_for(&self, opp: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.shapes.len()).find(|&own| self.outcome(opp, own) == outcome)
+    }
+
+    /// Indices of the opponent's shape and of the shape whose column letter is in the second
+    /// column, or `None` for a line that is not part of the guide.
+    #[must_use]
+    pub fn parse_line(&self, line: &str) -> Option<(usize, usize)> {
+        let mut words = line.split_whitespace();
+        let (opp, column) = (words.next()?, words.next()?);
+        if words.next().is_some() {
+            return None;
+        }
+        let letter = |s: &str| {
+            let mut chars = s.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        };
+        let (opp, column) = (letter(opp)?, letter(column)?);
+        Some((
+            self.shapes.iter().position(|s| s.opponent == opp)?,
+            self.shapes.iter().position(|s| s.column == column)?,
+        ))
+    }
+
+    /// The shape to play for a line of the guide.
+    #[must_use]
+    pub fn decode(&self, opp: usize, column: usize, decoding: Decoding) -> Option<usize> {
+        match decoding {
+            Decoding::Shape => Some(column),
+            Decoding::Outcome => self.shape_for(opp, *Outcome::ALL.get(column)?),
+        }
+    }
+
+    /// Score of following the whole guide, skipping lines that can't be decoded.
+    #[must_use]
+    pub fn total_score(&self, guide: &str, decoding: Decoding) -> u32 {
+        guide
+            .lines()
+            .filter_map(|line| self.parse_line(line))
+            .filter_map(|(opp, column)| Some((opp, self.decode(opp, column, decoding)?)))
+            .map(|(opp, own)| self.score(opp, own))
+            .sum()
+    }
+
+    /// How many lines of the guide have each opponent shape and column letter, indexed by the
+    /// opponent's shape and then the column.
+    #[must_use]
+    pub fn pair_counts(&self, guide: &str) -> Vec<Vec<u32>> {
+        let mut counts = vec![vec![0; self.shapes.len()]; self.shapes.len()];
+        for (opp, column) in guide.lines().filter_map(|line| self.parse_line(line)) {
+            counts[opp][column] += 1;
+        }
+        counts
+    }
+
+    /// Highest score reachable by choosing the best shape against every line of the guide.
+    #[must_use]
+    pub fn max_score(&self, guide: &str) -> u32 {
+        self.per_opponent(guide)
+            .map(|(opp, count)| {
+                let best = (0..self.shapes.len())
+                    .map(|own| self.score(opp, own))
+                    .max()
+                    .unwrap_or(0);
+                count * best
+            })
+            .sum()
+    }
+
+    /// Expected score of a player choosing a shape uniformly at random for every line.
+    #[must_use]
+    pub fn expected_random_score(&self, guide: &str) -> f64 {
+        self.per_opponent(guide)
+            .map(|(opp, count)| {
+                let total = (0..self.shapes.len())
+                    .map(|own| self.score(opp, own))
+                    .sum::<u32>();
+                f64::from(count) * f64::from(total) / f64::from(self.shape_count())
+            })
+            .sum()
+    }
+
+    /// Probability of every score, indexed by score, over all the ways of decoding each line of
+    /// the guide as one of the shapes. Probabilities too small for an `f64` come out as 0.
+    #[must_use]
+    pub fn score_distribution(&self, guide: &str) -> Vec<f64> {
+        let weight = 1.0 / f64::from(self.shape_count());
+        let mut distribution = vec![1.0];
+        for (opp, count) in self.per_opponent(guide).filter(|&(_, count)| count > 0) {
+            let highest = (0..self.shapes.len())
+                .map(|own| self.score(opp, own) as usize)
+                .max()
+                .unwrap_or(0);
+            let mut line = vec![0.0; highest + 1];
+            for own in 0..self.shapes.len() {
+                line[self.score(opp, own) as usize] += weight;
+            }
+            // Every line with the same opponent shape adds one of the same scores
+            distribution = convolve(&distribution, &power(line, count));
+        }
+        distribution
+    }
+
+    /// Number of lines against each opponent shape, whatever their column.
+    fn per_opponent(&self, guide: &str) -> impl Iterator<Item = (usize, u32)> {
+        self.pair_counts(guide)
+            .into_iter()
+            .map(|columns| columns.iter().sum())
+            .enumerate()
+    }
+
+    fn shape_count(&self) -> u32 {
+        u32::try_from(self.shapes.len()).unwrap_or(u32::MAX)
+    }
 }
 
-fn score(opp: u8, own: u8) -> u8 {
-    let outcome = ((4 + own - opp) % 3) * 3;
-    own + outcome
+/// Distribution of the sum of two independent scores.
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut sum = vec![0.0; a.len() + b.len() - 1];
+    for (i, &p) in a.iter().enumerate().filter(|&(_, &p)| p > 0.0) {
+        for (j, &q) in b.iter().enumerate() {
+            sum[i + j] += p * q;
+        }
+    }
+    sum
 }
 
-fn get_own(opp: u8, outcome: u8) -> u8 {
-    (opp + outcome) % 3 + 1
+/// Distribution of the sum of `times` independent scores distributed like `base`, by squaring.
+fn power(mut base: Vec<f64>, mut times: u32) -> Vec<f64> {
+    let mut result = vec![1.0];
+    while times > 0 {
+        if times & 1 == 1 {
+            result = convolve(&result, &base);
+        }
+        times >>= 1;
+        if times > 0 {
+            base = convolve(&base, &base);
+        }
+    }
+    result
 }
 
 #[cfg(test)]
@@ -60,4 +374,75 @@ mod test {
     fn test_part2() {
         assert_eq!(Day2.part2(TEST_INPUT), Some(12));
     }
+
+    #[test]
+    fn test_parse_line() {
+        let game = Game::rock_paper_scissors();
+        assert_eq!(game.parse_line("A Y"), Some((0, 1)));
+        assert_eq!(game.parse_line("A Y "), Some((0, 1)));
+        assert_eq!(game.parse_line(" B  X\r"), Some((1, 0)));
+        assert_eq!(game.parse_line("A Y Z"), None);
+        assert_eq!(game.parse_line("AY"), None);
+        assert_eq!(Day2.part1("A Y \nB X\nC Z  \n"), 15);
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        for opp in 0..5 {
+            let outcomes = (0..5).map(|own| game.outcome(opp, own)).collect::<Vec<_>>();
+            let count = |outcome| outcomes.iter().filter(|&&o| o == outcome).count();
+            assert_eq!((count(Outcome::Win), count(Outcome::Draw)), (2, 1));
+        }
+
+        // Spock vaporizes Rock, Lizard poisons Spock
+        let guide = "A V\nE W\nD W\n";
+        assert_eq!(
+            game.total_score(guide, Decoding::Shape),
+            (5 + 6) + (4 + 6) + (4 + 3)
+        );
+        // The fourth and fifth letters are not outcomes
+        assert_eq!(game.total_score("E Z\nE W\n", Decoding::Outcome), 2 + 6);
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        let shapes = || Game::rock_paper_scissors().shapes().to_vec();
+        let error = |beats: &[(&str, &str)]| Game::new(shapes(), beats, Scoring::STANDARD);
+        assert!(error(&[("Rock", "Rock")]).is_err());
+        assert!(error(&[("Rock", "Paper"), ("Paper", "Rock")]).is_err());
+        assert!(error(&[("Rock", "Well")]).is_err());
+
+        let mut duplicate = shapes();
+        duplicate[2].column = 'X';
+        assert!(Game::new(duplicate, &[], Scoring::STANDARD).is_err());
+    }
+
+    #[test]
+    fn test_analysis() {
+        let game = Game::rock_paper_scissors();
+        let counts = game.pair_counts(TEST_INPUT);
+        assert_eq!(counts, [[0, 1, 0], [1, 0, 0], [0, 0, 1]]);
+        assert_eq!(game.max_score(TEST_INPUT), 8 + 9 + 7);
+        assert!((game.expected_random_score(TEST_INPUT) - 15.0).abs() < 1e-9);
+
+        let distribution = game.score_distribution(TEST_INPUT);
+        assert_eq!(distribution.len(), 24 + 1);
+        assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((distribution[24] - 1.0 / 27.0).abs() < 1e-9);
+        let mean = distribution
+            .iter()
+            .enumerate()
+            .map(|(score, p)| f64::from(u32::try_from(score).unwrap()) * p)
+            .sum::<f64>();
+        assert!((mean - 15.0).abs() < 1e-9);
+        // The lowest score, 3 + 1 + 2, is reached in one decoding only
+        assert_eq!(distribution.iter().position(|&p| p > 0.0), Some(6));
+        assert!((distribution[6] - 1.0 / 27.0).abs() < 1e-9);
+
+        let long_guide = TEST_INPUT.repeat(1000);
+        let distribution = game.score_distribution(&long_guide);
+        assert_eq!(distribution.len(), 24 * 1000 + 1);
+        assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
 }
@@ -17,12 +17,29 @@ impl Solution<20> for Day20 {
     }
 }
 
+/// Mixes through an indexed doubly linked list instead of rotating a `VecDeque`.
+pub struct Day20LinkedList;
+
+impl Solution<20> for Day20LinkedList {
+    type Output = i64;
+
+    fn part1(&self, input: &str) -> Self::Output {
+        let input: Vec<i64> = input.lines().filter_map(|l| l.parse().ok()).collect();
+        decrypt_linked(&input, 1, 1).unwrap()
+    }
+
+    fn part2(&self, input: &str) -> Option<Self::Output> {
+        let input: Vec<i64> = input.lines().filter_map(|l| l.parse().ok()).collect();
+        decrypt_linked(&input, 811_589_153, 10)
+    }
+}
+
 struct Entry {
     value: i64,
     index: usize,
 }
 
-#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
 fn decrypt(input: &[i64], key: i64, iterations: usize) -> Option<i64> {
     let mut entries = input
         .iter()
@@ -51,6 +68,59 @@ fn decrypt(input: &[i64], key: i64, iterations: usize) -> Option<i64> {
     Some(sum)
 }
 
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+fn decrypt_linked(input: &[i64], key: i64, iterations: usize) -> Option<i64> {
+    let len = input.len();
+    let values = input.iter().map(|value| value * key).collect::<Vec<_>>();
+    let mut next = (0..len).map(|i| (i + 1) % len).collect::<Vec<_>>();
+    let mut prev = (0..len).map(|i| (i + len - 1) % len).collect::<Vec<_>>();
+
+    // A moving entry skips over the other len - 1 entries
+    let others = len.checked_sub(1)?;
+    for _ in 0..iterations {
+        for (idx, value) in values.iter().enumerate() {
+            let shift = value.rem_euclid(others.max(1) as i64) as usize;
+            if shift == 0 {
+                continue;
+            }
+            let (before, after) = (prev[idx], next[idx]);
+            next[before] = after;
+            prev[after] = before;
+
+            let mut target = before;
+            if shift <= others / 2 {
+                for _ in 0..shift {
+                    target = next[target];
+                }
+            } else {
+                for _ in 0..others - shift {
+                    target = prev[target];
+                }
+            }
+
+            let after = next[target];
+            next[target] = idx;
+            prev[idx] = target;
+            next[idx] = after;
+            prev[after] = idx;
+        }
+    }
+
+    let mut current = values.iter().position(|&value| value == 0)?;
+    let mut sum = 0;
+    for _ in 1..=3 {
+        for _ in 0..1000 % len {
+            current = next[current];
+        }
+        sum += values[current];
+    }
+    Some(sum)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -75,4 +145,10 @@ mod test {
     fn test_part2() {
         assert_eq!(Day20.part2(TEST_INPUT), Some(1_623_178_306));
     }
+
+    #[test]
+    fn test_linked_list() {
+        assert_eq!(Day20LinkedList.part1(TEST_INPUT), 3);
+        assert_eq!(Day20LinkedList.part2(TEST_INPUT), Some(1_623_178_306));
+    }
 }
@@ -20,18 +20,49 @@ impl Solution<21> for Day21 {
     type Output = i64;
 
     fn part1(&self, input: &str) -> Self::Output {
-        let monkeys = iterator(input, terminated(parse_monkey, multispace0))
-            .map(|m| (m.name.clone(), m))
-            .collect::<FxHashMap<_, _>>();
-        let root = &monkeys["root"];
-        root.calculate(&monkeys).unwrap()
+        MonkeyMap::parse(input).value(ROOT_NAME).unwrap()
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
+        MonkeyMap::parse(input).human_value()
+    }
+}
+
+/// The parsed monkeys of an input, by name.
+pub struct MonkeyMap {
+    monkeys: FxHashMap<String, Monkey<i64>>,
+}
+
+impl MonkeyMap {
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
         let monkeys = iterator(input, terminated(parse_monkey, multispace0))
             .map(|m| (m.name.clone(), m))
             .collect::<FxHashMap<_, _>>();
-        let root = &monkeys["root"];
+        Self { monkeys }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.monkeys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.monkeys.is_empty()
+    }
+
+    /// The number the monkey called `name` yells.
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<i64> {
+        self.monkeys.get(name)?.calculate(&self.monkeys)
+    }
+
+    /// The number the human has to yell so that both sides of the root monkey are equal.
+    #[must_use]
+    pub fn human_value(&self) -> Option<i64> {
+        let monkeys = &self.monkeys;
+        let root = monkeys.get(ROOT_NAME)?;
 
         let mut values = FxHashMap::<String, i64>::default();
         let MonkeyExpression::Expression(root_calc) = &root.expression else {
@@ -40,13 +71,13 @@ impl Solution<21> for Day21 {
 
         let left = monkeys.get(&root_calc.left)?;
         let right = monkeys.get(&root_calc.right)?;
-        let left_val = left.calculate_equation_tree(&monkeys, &mut values);
-        let right_val = right.calculate_equation_tree(&monkeys, &mut values);
+        let left_val = left.calculate_equation_tree(monkeys, &mut values);
+        let right_val = right.calculate_equation_tree(monkeys, &mut values);
 
         if let Some(left_val) = left_val {
-            right.solve_for_human(&monkeys, &mut values, left_val)
+            right.solve_for_human(monkeys, &mut values, left_val)
         } else if let Some(right_val) = right_val {
-            left.solve_for_human(&monkeys, &mut values, right_val)
+            left.solve_for_human(monkeys, &mut values, right_val)
         } else {
             None
         }
@@ -59,6 +90,7 @@ struct Monkey<T> {
     expression: MonkeyExpression<T>,
 }
 
+const ROOT_NAME: &str = "root";
 const HUMAN_NAME: &str = "humn";
 
 impl<T> Monkey<T>
@@ -311,4 +343,13 @@ mod test {
     fn test_part2() {
         assert_eq!(Day21.part2(TEST_INPUT), Some(301));
     }
+
+    #[test]
+    fn test_monkey_map() {
+        let monkeys = MonkeyMap::parse(TEST_INPUT);
+        assert_eq!(monkeys.len(), 15);
+        assert_eq!(monkeys.value("sjmn"), Some(150));
+        assert_eq!(monkeys.value("pppw"), Some(2));
+        assert_eq!(monkeys.value("nope"), None);
+    }
 }
@@ -51,7 +51,6 @@ impl Solution<22> for Day22 {
         state.password()
     }
 
-    #[allow(clippy::match_on_vec_items)]
     fn part2(&self, input: &str) -> Option<Self::Output> {
         let (grid, instructions) = input.split_once("\n\n").unwrap();
         let (grid, _, _) = parse_grid(grid);
@@ -294,8 +293,9 @@ fn parse_grid(input: &str) -> (Vec<Vec<Tile>>, isize, isize) {
         .collect::<Vec<_>>();
     let length = grid.len() as isize;
     let width = grid.iter().map(Vec::len).max().unwrap() as isize;
-    grid.iter_mut()
-        .for_each(|row| row.resize_with(width as usize, || Tile::Void));
+    for row in &mut grid {
+        row.resize_with(width as usize, || Tile::Void);
+    }
     (grid, length, width)
 }
 
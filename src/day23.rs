@@ -37,7 +37,7 @@ where
     S: BuildHasher + Default,
 {
     let mut next_step = HashSet::with_capacity_and_hasher(elves.capacity(), S::default());
-    for &elf in elves.iter() {
+    for &elf in elves {
         let next_pos = find_destination(elves, elf, round);
         if next_pos == elf {
             next_step.insert(elf);
@@ -69,7 +69,7 @@ where
 fn bounding_box<S>(elves: &HashSet<Position, S>) -> (usize, usize) {
     let (mut min_x, mut max_x, mut min_y, mut max_y) = (i32::MAX, i32::MIN, i32::MAX, i32::MIN);
 
-    for elf in elves.iter() {
+    for elf in elves {
         min_x = min(min_x, elf.0);
         max_x = max(max_x, elf.0);
         min_y = min(min_y, elf.1);
@@ -1,4 +1,5 @@
 use crate::Solution;
+use std::fmt::{Display, Formatter};
 
 pub struct Day3;
 
@@ -8,26 +9,227 @@ impl Solution<3> for Day3 {
     fn part1(&self, input: &str) -> Self::Output {
         input
             .lines()
-            .map(|l| l.split_at(l.len() / 2))
-            .filter_map(|(c1, c2)| c1.chars().find(|c: &char| c2.contains(&c.to_string())))
-            .filter_map(priority)
+            .filter_map(shared_in_compartments)
+            .map(ItemSet::priority_sum)
             .sum()
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
-        let sum = input
-            .lines()
-            .array_chunks::<3>()
-            .filter_map(|chunks: [&str; 3]| {
-                chunks[0].chars().find(|c| {
-                    chunks[1].contains(&c.to_string()) && chunks[2].contains(&c.to_string())
-                })
-            })
-            .filter_map(priority)
-            .sum();
+        Some(badges(input, 3).map(ItemSet::priority_sum).sum())
+    }
+}
+
+/// A set of items, as a bitmask with the bit of each item's priority set.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    /// The items of `items`, ignoring characters that are not items.
+    #[must_use]
+    pub fn new(items: &str) -> Self {
+        items.chars().collect()
+    }
+
+    /// Items that are in every one of `sets`, or none if there are no sets.
+    pub fn intersection_of(sets: impl IntoIterator<Item = ItemSet>) -> Self {
+        let mut sets = sets.into_iter();
+        let first = sets.next().unwrap_or(Self::EMPTY);
+        sets.fold(first, Self::intersection)
+    }
+
+    /// Items that are in any of `sets`.
+    pub fn union_of(sets: impl IntoIterator<Item = ItemSet>) -> Self {
+        sets.into_iter().fold(Self::EMPTY, Self::union)
+    }
+
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Adds `item`, returning whether it is an item at all.
+    pub fn insert(&mut self, item: char) -> bool {
+        priority(item).map(|p| self.0 |= 1 << p).is_some()
+    }
+
+    #[must_use]
+    pub fn contains(self, item: char) -> bool {
+        priority(item).is_some_and(|p| self.0 & (1 << p) != 0)
+    }
+
+    #[must_use]
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities of the items, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |p| self.0 & (1 << p) != 0)
+    }
+
+    /// The items, by priority.
+    pub fn items(self) -> impl Iterator<Item = char> {
+        (1..=52u8)
+            .filter(move |p| self.0 & (1 << p) != 0)
+            .map(|p| char::from(if p <= 26 { b'a' + p - 1 } else { b'A' + p - 27 }))
+    }
+
+    #[must_use]
+    pub fn priority_sum(self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut set = Self::EMPTY;
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+/// Items in both compartments of a rucksack, or `None` if it can't be split in half.
+#[must_use]
+pub fn shared_in_compartments(rucksack: &str) -> Option<ItemSet> {
+    let (first, second) = rucksack.split_at_checked(rucksack.len() / 2)?;
+    Some(ItemSet::new(first).intersection(ItemSet::new(second)))
+}
+
+/// Items shared by every rucksack of each group of `size` lines, dropping a trailing partial group.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn badges(input: &str, size: usize) -> impl Iterator<Item = ItemSet> + '_ {
+    assert!(size > 0, "groups need at least one rucksack");
+    let rucksacks = input.lines().map(ItemSet::new).collect::<Vec<_>>();
+    (0..rucksacks.len() / size).map(move |group| {
+        ItemSet::intersection_of(rucksacks[group * size..][..size].iter().copied())
+    })
+}
+
+/// Something wrong with the input that the solution silently skips over.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Problem {
+    OddLength {
+        line: usize,
+        len: usize,
+    },
+    InvalidItem {
+        line: usize,
+        item: char,
+    },
+    /// The compartments share no item or more than one.
+    SharedItems {
+        line: usize,
+        shared: ItemSet,
+    },
+    /// The rucksacks of a group share no item or more than one.
+    Badges {
+        first_line: usize,
+        shared: ItemSet,
+    },
+    IncompleteGroup {
+        first_line: usize,
+        rucksacks: usize,
+    },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let items = |set: &ItemSet| set.items().map(String::from).collect::<Vec<_>>().join(", ");
+        match self {
+            Problem::OddLength { line, len } => {
+                write!(f, "line {line}: odd number of items ({len})")
+            }
+            Problem::InvalidItem { line, item } => write!(f, "line {line}: invalid item {item:?}"),
+            Problem::SharedItems { line, shared } if shared.is_empty() => {
+                write!(f, "line {line}: no item in both compartments")
+            }
+            Problem::SharedItems { line, shared } => {
+                write!(
+                    f,
+                    "line {line}: {} items in both compartments: {}",
+                    shared.len(),
+                    items(shared)
+                )
+            }
+            Problem::Badges { first_line, shared } if shared.is_empty() => {
+                write!(f, "group at line {first_line}: no badge")
+            }
+            Problem::Badges { first_line, shared } => {
+                write!(
+                    f,
+                    "group at line {first_line}: {} badges: {}",
+                    shared.len(),
+                    items(shared)
+                )
+            }
+            Problem::IncompleteGroup { first_line, .. } => {
+                write!(f, "group at line {first_line}: incomplete")
+            }
+        }
+    }
+}
 
-        Some(sum)
+/// Every problem with the rucksacks and their groups of `size`, in input order. Line numbers
+/// start at 1.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+#[must_use]
+pub fn validate(input: &str, size: usize) -> Vec<Problem> {
+    assert!(size > 0, "groups need at least one rucksack");
+    let mut problems = Vec::new();
+    let lines = input.lines().collect::<Vec<_>>();
+    for (group_index, group) in lines.chunks(size).enumerate() {
+        let first_line = group_index * size + 1;
+        for (line, rucksack) in (first_line..).zip(group) {
+            let invalid = rucksack.chars().filter(|&c| priority(c).is_none());
+            let invalid = invalid.map(|item| Problem::InvalidItem { line, item });
+            let before = problems.len();
+            problems.extend(invalid);
+
+            let len = rucksack.chars().count();
+            if len % 2 == 1 {
+                problems.push(Problem::OddLength { line, len });
+            } else if problems.len() == before {
+                let shared = shared_in_compartments(rucksack).unwrap_or_default();
+                if shared.len() != 1 {
+                    problems.push(Problem::SharedItems { line, shared });
+                }
+            }
+        }
+
+        if group.len() < size {
+            problems.push(Problem::IncompleteGroup {
+                first_line,
+                rucksacks: group.len(),
+            });
+        } else {
+            let shared = ItemSet::intersection_of(group.iter().map(|r| ItemSet::new(r)));
+            if shared.len() != 1 {
+                problems.push(Problem::Badges { first_line, shared });
+            }
+        }
     }
+    problems
 }
 
 fn priority(item: char) -> Option<u32> {
@@ -64,4 +266,75 @@ mod test {
     fn test_part2() {
         assert_eq!(Day3.part2(TEST_INPUT), Some(70));
     }
+
+    #[test]
+    fn test_item_set() {
+        let set = ItemSet::new("vJrwpWtwJgWr");
+        assert_eq!(set.len(), 8);
+        assert!(set.contains('J') && !set.contains('x') && !set.contains('1'));
+        assert_eq!(set.items().collect::<String>(), "gprtvwJW");
+
+        let shared = shared_in_compartments("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(shared.items().collect::<Vec<_>>(), ['p']);
+        assert_eq!(shared.priority_sum(), 16);
+
+        let many = shared_in_compartments("abcXabcY").unwrap();
+        assert_eq!(many.items().collect::<String>(), "abc");
+        assert_eq!(ItemSet::new("a1 !"), ItemSet::new("a"));
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert_eq!(ItemSet::intersection_of([]), ItemSet::EMPTY);
+        assert_eq!(
+            ItemSet::union_of([ItemSet::new("ab"), ItemSet::new("bZ")]),
+            ItemSet::new("abZ")
+        );
+    }
+
+    #[test]
+    fn test_badges() {
+        let badges3 = badges(TEST_INPUT, 3)
+            .flat_map(ItemSet::items)
+            .collect::<String>();
+        assert_eq!(badges3, "rZ");
+        assert_eq!(badges(TEST_INPUT, 4).count(), 1);
+        assert_eq!(
+            badges(TEST_INPUT, 1).next(),
+            Some(ItemSet::new(TEST_INPUT.lines().next().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(TEST_INPUT, 3), []);
+        assert_eq!(
+            validate("abab\n", 2)[1],
+            Problem::IncompleteGroup {
+                first_line: 1,
+                rucksacks: 1
+            }
+        );
+
+        let input = indoc! {"
+            abcabc
+            aBcdXy1z
+            abcde
+            abZdeZ
+            xaxZyZ
+            "};
+        let problems = validate(input, 2)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            [
+                "line 1: 3 items in both compartments: a, b, c",
+                "line 2: invalid item '1'",
+                "group at line 1: 2 badges: a, c",
+                "line 3: odd number of items (5)",
+                "group at line 3: 4 badges: a, b, d, e",
+                "line 5: no item in both compartments",
+                "group at line 5: incomplete",
+            ]
+        );
+    }
 }
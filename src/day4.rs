@@ -1,5 +1,6 @@
+use crate::interval::{Interval, IntervalSet};
 use crate::Solution;
-use std::ops::RangeInclusive;
+use std::cmp::Reverse;
 
 pub struct Day4;
 
@@ -11,42 +12,133 @@ impl Solution<4> for Day4 {
     }
 
     fn part2(&self, input: &str) -> Option<Self::Output> {
-        Some(filter_count_ranges(input, RangeCheck::overlaps))
+        Some(filter_count_ranges(input, Interval::overlaps))
     }
 }
 
-fn filter_count_ranges<P>(input: &str, predicate: P) -> usize
-where
-    P: Fn(&RangeInclusive<u8>, &RangeInclusive<u8>) -> bool,
-{
+/// The pair of section assignments on each line, skipping lines that are not a pair.
+pub fn assignments(input: &str) -> impl Iterator<Item = (Interval<u32>, Interval<u32>)> + '_ {
     input
         .lines()
         .filter_map(|l| l.split_once(','))
         .filter_map(|(l, r)| Some((parse_range(l)?, parse_range(r)?)))
-        .filter(|(l, r)| predicate(l, r))
-        .count()
 }
 
-fn parse_range(elf: &str) -> Option<RangeInclusive<u8>> {
-    let (begin, end) = elf.split_once('-')?;
-    let begin = begin.parse::<u8>().ok()?;
-    let end = end.parse::<u8>().ok()?;
-    Some(begin..=end)
+/// Sections between the lowest and highest assigned ones that no elf is assigned to.
+#[must_use]
+pub fn uncovered_sections(input: &str) -> IntervalSet<u32> {
+    let covered = assignments(input)
+        .flat_map(|(l, r)| [l, r])
+        .collect::<IntervalSet<_>>();
+    let bounds = match covered.intervals() {
+        [first, .., last] => Interval::new(first.start(), last.end()),
+        _ => None,
+    };
+    bounds.map_or_else(IntervalSet::new, |bounds| covered.gaps(bounds))
 }
 
-trait RangeCheck {
-    fn fully_contains(&self, other: &Self) -> bool;
-    fn overlaps(&self, other: &Self) -> bool;
+/// What a sweep over every elf's assignment finds. Elves are numbered in input order, two per
+/// line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Analysis {
+    /// Most elves assigned to any single section.
+    pub max_elves: usize,
+    /// Sections with `max_elves` elves assigned.
+    pub busiest: IntervalSet<u32>,
+    /// How many other elves fully contain each elf's assignment.
+    pub contained_by: Vec<usize>,
 }
 
-impl<T: Ord> RangeCheck for RangeInclusive<T> {
-    fn fully_contains(&self, other: &Self) -> bool {
-        self.start() <= other.start() && self.end() >= other.end()
+/// Sweeps over all assignments at once in O(n log n).
+#[must_use]
+pub fn analyze(input: &str) -> Analysis {
+    let elves = assignments(input)
+        .flat_map(|(l, r)| [l, r])
+        .collect::<Vec<_>>();
+    let (max_elves, busiest) = busiest_sections(&elves);
+    Analysis {
+        max_elves,
+        busiest,
+        contained_by: contained_by(&elves),
     }
+}
+
+fn busiest_sections(elves: &[Interval<u32>]) -> (usize, IntervalSet<u32>) {
+    // Assignments starting at a section count before those ending at it
+    let mut events = elves
+        .iter()
+        .flat_map(|elf| [(elf.start(), false), (elf.end(), true)])
+        .collect::<Vec<_>>();
+    events.sort_unstable();
 
-    fn overlaps(&self, other: &Self) -> bool {
-        self.start() <= other.end() && self.end() >= other.start()
+    let (mut count, mut max) = (0, 0);
+    let mut busiest = Vec::new();
+    let mut busy_since = 0;
+    for (section, is_end) in events {
+        if is_end {
+            if count == max {
+                busiest.extend(Interval::new(busy_since, section));
+            }
+            count -= 1;
+        } else {
+            count += 1;
+            if count > max {
+                max = count;
+                busiest.clear();
+            }
+            if count == max {
+                busy_since = section;
+            }
+        }
     }
+    (max, busiest.into_iter().collect())
+}
+
+fn contained_by(elves: &[Interval<u32>]) -> Vec<usize> {
+    // Containing assignments sort before the ones they contain
+    let mut order = (0..elves.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&elf| (elves[elf].start(), Reverse(elves[elf].end())));
+    let mut ends = elves.iter().map(Interval::end).collect::<Vec<_>>();
+    ends.sort_unstable();
+    ends.dedup();
+
+    // Fenwick tree counting the ends seen so far, by their rank in `ends`
+    let mut tree = vec![0; ends.len() + 1];
+    let mut seen = 0;
+    let mut contained_by = vec![0; elves.len()];
+    for group in order.chunk_by(|&a, &b| elves[a] == elves[b]) {
+        let rank = ends.partition_point(|&end| end < elves[group[0]].end());
+        // Seen ends below this one, the rest are at least as far
+        let (mut below, mut i) = (0, rank);
+        while i > 0 {
+            below += tree[i];
+            i &= i - 1;
+        }
+        for &elf in group {
+            // Identical assignments contain each other
+            contained_by[elf] = seen - below + group.len() - 1;
+        }
+
+        let mut i = rank + 1;
+        while i < tree.len() {
+            tree[i] += group.len();
+            i += i & i.wrapping_neg();
+        }
+        seen += group.len();
+    }
+    contained_by
+}
+
+fn filter_count_ranges<P>(input: &str, predicate: P) -> usize
+where
+    P: Fn(&Interval<u32>, &Interval<u32>) -> bool,
+{
+    assignments(input).filter(|(l, r)| predicate(l, r)).count()
+}
+
+fn parse_range(elf: &str) -> Option<Interval<u32>> {
+    let (begin, end) = elf.split_once('-')?;
+    Interval::new(begin.parse().ok()?, end.parse().ok()?)
 }
 
 #[cfg(test)]
@@ -72,4 +164,45 @@ mod test {
     fn test_part2() {
         assert_eq!(Day4.part2(TEST_INPUT), Some(4));
     }
+
+    #[test]
+    fn test_large_sections() {
+        assert_eq!(Day4.part1("200-300,250-260\n1-1000,2000-3000\n"), 1);
+    }
+
+    #[test]
+    fn test_uncovered_sections() {
+        assert!(uncovered_sections(TEST_INPUT).is_empty());
+        let uncovered = uncovered_sections("2-3,10-12\n5-5,11-14\n");
+        let gaps = uncovered
+            .intervals()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(gaps, ["4-4", "6-9"]);
+        assert_eq!(uncovered.coverage(), 5);
+        assert!(uncovered_sections("").is_empty());
+    }
+
+    #[test]
+    fn test_analyze() {
+        let analysis = analyze(TEST_INPUT);
+        assert_eq!(analysis.max_elves, 8);
+        let busiest = analysis
+            .busiest
+            .intervals()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(busiest, ["6-6"]);
+        assert_eq!(analysis.contained_by, [2, 2, 3, 5, 3, 0, 0, 1, 7, 4, 1, 1]);
+
+        let same = analyze("1-5,1-5\n2-3,1-5\n");
+        assert_eq!(same.contained_by, [2, 2, 3, 2]);
+        assert_eq!(same.max_elves, 4);
+        assert_eq!(same.busiest.intervals(), [Interval::new(2, 3).unwrap()]);
+
+        let empty = analyze("");
+        assert_eq!((empty.max_elves, empty.busiest.is_empty()), (0, true));
+    }
 }
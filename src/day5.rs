@@ -1,38 +1,356 @@
 use crate::Solution;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::iter::repeat_with;
 
-enum Crane {
-    CrateMover9000,
-    CrateMover9001,
+/// How a crane puts down the crates of a move.
+pub trait CraneModel {
+    /// Name that selects the crane, see [`parse_crane`].
+    fn name(&self) -> String;
+
+    /// Rearranges `crates`, bottom first as they stood on the source stack, into the order they
+    /// end up in on the target stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crane can't move that many crates.
+    fn arrange(&self, crates: &mut [char]) -> Result<(), MoveError>;
+}
+
+/// Moves one crate at a time.
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn name(&self) -> String {
+        "9000".to_string()
+    }
+
+    fn arrange(&self, crates: &mut [char]) -> Result<(), MoveError> {
+        crates.reverse();
+        Ok(())
+    }
+}
+
+/// Moves all the crates at once.
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn name(&self) -> String {
+        "9001".to_string()
+    }
+
+    fn arrange(&self, _crates: &mut [char]) -> Result<(), MoveError> {
+        Ok(())
+    }
+}
+
+/// Moves up to `size` crates at a time, starting from the top.
+pub struct BatchCrane {
+    pub size: usize,
+}
+
+impl CraneModel for BatchCrane {
+    fn name(&self) -> String {
+        format!("batch:{}", self.size)
+    }
+
+    fn arrange(&self, crates: &mut [char]) -> Result<(), MoveError> {
+        let arranged = crates
+            .rchunks(self.size.max(1))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        crates.copy_from_slice(&arranged);
+        Ok(())
+    }
+}
+
+/// Moves all the crates at once, but no more than `capacity` of them.
+pub struct CapacityCrane {
+    pub capacity: usize,
+}
+
+impl CraneModel for CapacityCrane {
+    fn name(&self) -> String {
+        format!("capacity:{}", self.capacity)
+    }
+
+    fn arrange(&self, crates: &mut [char]) -> Result<(), MoveError> {
+        if crates.len() > self.capacity {
+            return Err(MoveError::OverCapacity {
+                capacity: self.capacity,
+                requested: crates.len(),
+            });
+        }
+        Ok(())
+    }
 }
 
+/// Moves all the crates at once, turning the batch so its top `by` crates end up at the bottom.
+pub struct RotatingCrane {
+    pub by: usize,
+}
+
+impl CraneModel for RotatingCrane {
+    fn name(&self) -> String {
+        format!("rotate:{}", self.by)
+    }
+
+    fn arrange(&self, crates: &mut [char]) -> Result<(), MoveError> {
+        if !crates.is_empty() {
+            crates.rotate_right(self.by % crates.len());
+        }
+        Ok(())
+    }
+}
+
+/// Selects a crane by name: `9000`, `9001`, `batch:<size>`, `capacity:<crates>` or
+/// `rotate:<crates>`.
+///
+/// # Errors
+///
+/// Returns an error if there is no such crane.
+pub fn parse_crane(name: &str) -> Result<Box<dyn CraneModel>, String> {
+    let (model, arg) = name.split_once(':').unwrap_or((name, ""));
+    let number = || {
+        arg.parse::<usize>()
+            .map_err(|_| format!("Crane {model} needs a number, as in {model}:3"))
+    };
+    Ok(match model {
+        "9000" if arg.is_empty() => Box::new(CrateMover9000),
+        "9001" if arg.is_empty() => Box::new(CrateMover9001),
+        "batch" => match number()? {
+            0 => return Err("Batches need at least one crate".to_string()),
+            size => Box::new(BatchCrane { size }),
+        },
+        "capacity" => Box::new(CapacityCrane {
+            capacity: number()?,
+        }),
+        "rotate" => Box::new(RotatingCrane { by: number()? }),
+        _ => {
+            return Err(format!(
+                "Unknown crane {name}, expected 9000, 9001, batch:<size>, capacity:<crates> or \
+                 rotate:<crates>"
+            ))
+        }
+    })
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MoveError {
+    UnknownStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        available: usize,
+        requested: usize,
+    },
+    OverCapacity {
+        capacity: usize,
+        requested: usize,
+    },
+    /// Seeking past the end of the procedure.
+    OutOfRange {
+        position: usize,
+        moves: usize,
+    },
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::UnknownStack(stack) => write!(f, "there is no stack {stack}"),
+            MoveError::NotEnoughCrates {
+                stack,
+                available,
+                requested,
+            } => write!(
+                f,
+                "stack {stack} holds {available} crates, the move needs {requested}"
+            ),
+            MoveError::OverCapacity {
+                capacity,
+                requested,
+            } => write!(
+                f,
+                "the crane lifts at most {capacity} crates, the move needs {requested}"
+            ),
+            MoveError::OutOfRange { position, moves } => {
+                write!(f, "can't seek to move {position}, there are {moves} moves")
+            }
+        }
+    }
+}
+
+impl Error for MoveError {}
+
 pub struct Day5;
 
 impl Solution<5> for Day5 {
     type Output = String;
 
     fn part1(&self, input: &str) -> Self::Output {
-        let (stacks, moves) = input.split_once("\n\n").unwrap();
-        let mut stacks = parse_stacks(stacks);
-        let moves = Move::parse_moves(moves);
+        rearrange(input, Box::new(CrateMover9000))
+    }
 
-        for m in moves {
-            m.apply_to(&mut stacks, &Crane::CrateMover9000);
-        }
+    fn part2(&self, input: &str) -> Option<Self::Output> {
+        Some(rearrange(input, Box::new(CrateMover9001)))
+    }
+}
+
+fn rearrange(input: &str, crane: Box<dyn CraneModel>) -> String {
+    let mut yard = CrateYard::parse(input, crane).unwrap_or_else(|e| panic!("{e}"));
+    yard.seek(yard.moves().len()).unwrap_or_else(|e| {
+        let number = yard.position() + 1;
+        panic!("Move {number} ({}): {e}", yard.moves()[number - 1])
+    });
+    yard.tops()
+}
 
-        stacks.iter().filter_map(|s| s.last()).collect()
+/// The stacks of crates part way through the rearrangement procedure.
+pub struct CrateYard {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+    crane: Box<dyn CraneModel>,
+    /// Crates picked up by each applied move, in their order on the stack, to undo it.
+    taken: Vec<Vec<char>>,
+}
+
+impl CrateYard {
+    /// Parses the drawing and the procedure, without applying any move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drawing is not followed by a blank line.
+    pub fn parse(input: &str, crane: Box<dyn CraneModel>) -> Result<Self, String> {
+        let (stacks, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| "Expected a blank line after the drawing".to_string())?;
+        Ok(Self {
+            stacks: parse_stacks(stacks),
+            moves: Move::parse_moves(moves).collect(),
+            crane,
+            taken: Vec::new(),
+        })
     }
 
-    fn part2(&self, input: &str) -> Option<Self::Output> {
-        let (stacks, moves) = input.split_once("\n\n").unwrap();
-        let mut stacks = parse_stacks(stacks);
-        let moves = Move::parse_moves(moves);
+    #[must_use]
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.stacks
+    }
+
+    #[must_use]
+    pub fn crane(&self) -> &dyn CraneModel {
+        self.crane.as_ref()
+    }
 
-        for m in moves {
-            m.apply_to(&mut stacks, &Crane::CrateMover9001);
+    #[must_use]
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Number of moves applied so far.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.taken.len()
+    }
+
+    /// The crate on top of every stack that has one.
+    #[must_use]
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|s| s.last()).collect()
+    }
+
+    /// Applies the next move, returning `false` at the end of the procedure. The stacks are left
+    /// alone if the move fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the next move can't be made.
+    pub fn redo(&mut self) -> Result<bool, MoveError> {
+        let Some(&m) = self.moves.get(self.position()) else {
+            return Ok(false);
+        };
+        let from = self.stack_index(m.from)?;
+        let to = self.stack_index(m.to)?;
+        let available = self.stacks[from].len();
+        let first = available
+            .checked_sub(m.count)
+            .ok_or(MoveError::NotEnoughCrates {
+                stack: m.from,
+                available,
+                requested: m.count,
+            })?;
+
+        let mut crates = self.stacks[from][first..].to_vec();
+        self.crane.arrange(&mut crates)?;
+        let taken = self.stacks[from].split_off(first);
+        self.stacks[to].extend(crates);
+        self.taken.push(taken);
+        Ok(true)
+    }
+
+    fn stack_index(&self, stack: usize) -> Result<usize, MoveError> {
+        stack
+            .checked_sub(1)
+            .filter(|&index| index < self.stacks.len())
+            .ok_or(MoveError::UnknownStack(stack))
+    }
+
+    /// Takes back the last applied move, returning `false` at the start of the procedure.
+    pub fn undo(&mut self) -> bool {
+        let Some(taken) = self.taken.pop() else {
+            return false;
+        };
+        let m = &self.moves[self.taken.len()];
+        let to = &mut self.stacks[m.to - 1];
+        to.truncate(to.len() - taken.len());
+        self.stacks[m.from - 1].extend(taken);
+        true
+    }
+
+    /// Applies or takes back moves until `position` of them are applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer moves than `position`, without moving anything, or
+    /// why a move fails. The moves before the failing one stay applied, so it is the one at
+    /// [`CrateYard::position`].
+    pub fn seek(&mut self, position: usize) -> Result<(), MoveError> {
+        if position > self.moves.len() {
+            return Err(MoveError::OutOfRange {
+                position,
+                moves: self.moves.len(),
+            });
         }
+        while self.position() > position && self.undo() {}
+        while self.position() < position && self.redo()? {}
+        Ok(())
+    }
 
-        Some(stacks.iter().filter_map(|s| s.last()).collect())
+    /// Draws the stacks the way the puzzle input does.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                let row = self
+                    .stacks
+                    .iter()
+                    .map(|stack| {
+                        stack
+                            .get(level)
+                            .map_or("   ".to_string(), |c| format!("[{c}]"))
+                    })
+                    .collect::<Vec<_>>();
+                row.join(" ").trim_end().to_string()
+            })
+            .collect::<Vec<_>>();
+        let numbers = (1..=self.stacks.len())
+            .map(|n| format!(" {n} "))
+            .collect::<Vec<_>>();
+        lines.push(numbers.join(" ").trim_end().to_string());
+        lines.join("\n")
     }
 }
 
@@ -59,10 +377,12 @@ fn parse_stacks(input: &str) -> Vec<Vec<char>> {
     stacks
 }
 
-struct Move {
-    count: usize,
-    from: usize,
-    to: usize,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Move {
+    pub count: usize,
+    /// Stack numbers, starting at 1.
+    pub from: usize,
+    pub to: usize,
 }
 
 impl Move {
@@ -77,15 +397,11 @@ impl Move {
             })
         })
     }
+}
 
-    fn apply_to(&self, stacks: &mut [Vec<char>], crane: &Crane) {
-        let from = stacks.get_mut(self.from - 1).unwrap();
-        let mut crates = from.split_off(from.len() - self.count);
-        match crane {
-            Crane::CrateMover9000 => crates.reverse(),
-            Crane::CrateMover9001 => {}
-        }
-        stacks.get_mut(self.to - 1).unwrap().append(&mut crates);
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
     }
 }
 
@@ -115,4 +431,113 @@ mod test {
     fn test_part2() {
         assert_eq!(Day5.part2(TEST_INPUT), Some("MCD".to_string()));
     }
+
+    #[test]
+    fn test_crate_yard() {
+        let mut yard = CrateYard::parse(TEST_INPUT, Box::new(CrateMover9000)).unwrap();
+        let drawing = TEST_INPUT.split_once("\n\n").unwrap().0;
+        assert_eq!(yard.render(), drawing);
+        assert_eq!(yard.moves()[1].to_string(), "move 3 from 1 to 3");
+
+        assert_eq!((yard.redo(), yard.redo()), (Ok(true), Ok(true)));
+        assert_eq!(yard.position(), 2);
+        assert_eq!(
+            yard.render(),
+            "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3"
+        );
+        assert_eq!(parse_stacks(&yard.render()), yard.stacks());
+
+        yard.seek(4).unwrap();
+        assert_eq!((yard.position(), yard.tops().as_str()), (4, "CMZ"));
+        assert_eq!(
+            yard.seek(5),
+            Err(MoveError::OutOfRange {
+                position: 5,
+                moves: 4
+            })
+        );
+        assert_eq!(yard.position(), 4);
+        assert_eq!(yard.redo(), Ok(false));
+        yard.seek(0).unwrap();
+        assert_eq!(yard.render(), drawing);
+        assert!(!yard.undo());
+
+        // Undoing a CrateMover 9001 move keeps the order of the crates too
+        let mut yard = CrateYard::parse(TEST_INPUT, Box::new(CrateMover9001)).unwrap();
+        yard.seek(3).unwrap();
+        yard.undo();
+        yard.seek(4).unwrap();
+        assert_eq!(yard.tops(), "MCD");
+    }
+
+    #[test]
+    fn test_cranes() {
+        let arrange = |name: &str| {
+            let mut crates = ['a', 'b', 'c', 'd', 'e'];
+            parse_crane(name)
+                .unwrap()
+                .arrange(&mut crates)
+                .map(|()| crates.iter().collect::<String>())
+        };
+        assert_eq!(arrange("9000"), Ok("edcba".to_string()));
+        assert_eq!(arrange("9001"), Ok("abcde".to_string()));
+        assert_eq!(arrange("batch:1"), arrange("9000"));
+        assert_eq!(arrange("batch:2"), Ok("debca".to_string()));
+        assert_eq!(arrange("batch:5"), arrange("9001"));
+        assert_eq!(arrange("rotate:2"), Ok("deabc".to_string()));
+        assert_eq!(arrange("rotate:5"), arrange("9001"));
+        assert_eq!(arrange("capacity:5"), arrange("9001"));
+        assert_eq!(
+            arrange("capacity:4"),
+            Err(MoveError::OverCapacity {
+                capacity: 4,
+                requested: 5
+            })
+        );
+
+        for name in ["9000", "batch:3", "capacity:2", "rotate:1"] {
+            assert_eq!(parse_crane(name).unwrap().name(), name);
+        }
+        for name in ["9002", "9000:1", "batch", "batch:0", "rotate:x"] {
+            assert!(parse_crane(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_move_errors() {
+        let mut yard = CrateYard::parse(TEST_INPUT, parse_crane("capacity:2").unwrap()).unwrap();
+        assert_eq!(
+            yard.seek(4),
+            Err(MoveError::OverCapacity {
+                capacity: 2,
+                requested: 3
+            })
+        );
+        assert_eq!(yard.position(), 1);
+        assert!(registry_error(TEST_INPUT.replace("\n\n", "\n").as_str())
+            .contains("Expected a blank line after the drawing"));
+
+        let input = "[A]\n 1   2\n\nmove 2 from 1 to 2\nmove 1 from 3 to 1\n";
+        let mut yard = CrateYard::parse(input, Box::new(CrateMover9000)).unwrap();
+        assert_eq!(
+            yard.redo(),
+            Err(MoveError::NotEnoughCrates {
+                stack: 1,
+                available: 1,
+                requested: 2
+            })
+        );
+        assert_eq!(yard.stacks(), [vec!['A'], vec![]]);
+        assert!(registry_error(input).contains("Move 1 (move 2 from 1 to 2): stack 1 holds 1"));
+
+        let input = "[A]\n 1   2\n\nmove 1 from 3 to 1\n";
+        let mut yard = CrateYard::parse(input, Box::new(CrateMover9000)).unwrap();
+        assert_eq!(yard.redo(), Err(MoveError::UnknownStack(3)));
+    }
+
+    fn registry_error(input: &str) -> String {
+        crate::registry::solve(5, crate::Part::One, input)
+            .unwrap_err()
+            .to_string()
+    }
 }
@@ -1,3 +1,4 @@
+use crate::iter_ext::ArrayWindowedExt;
 use crate::Solution;
 use std::collections::HashSet;
 use std::hash::Hash;
@@ -20,7 +21,7 @@ fn n_distinct_after<const N: usize>(input: &str) -> usize {
     input
         .chars()
         .collect::<Vec<_>>()
-        .array_windows::<N>()
+        .array_windowed::<N>()
         .enumerate()
         .find(|(_, chars)| chars.iter().are_unique())
         .unwrap()
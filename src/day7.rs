@@ -38,6 +38,20 @@ impl Solution<7> for Day7 {
     }
 }
 
+/// Total size of every directory by its absolute path, starting with `/`.
+#[must_use]
+pub fn directory_sizes(input: &str) -> Vec<(String, usize)> {
+    let FileSystem {
+        fs,
+        root,
+        directories,
+    } = parse_fs(input);
+    std::iter::once(root)
+        .chain(directories)
+        .map(|dir| (FsEntry::get_path(&fs, dir), FsEntry::get_size(&fs, &dir)))
+        .collect()
+}
+
 struct FileSystem {
     fs: Arena<FsEntry>,
     root: NodeId,
@@ -112,6 +126,20 @@ impl FsEntry {
             FsEntry::File { size, .. } => *size,
         }
     }
+
+    fn get_path(fs: &Arena<FsEntry>, entry: NodeId) -> String {
+        let mut names = entry
+            .ancestors(fs)
+            .filter_map(|a| match fs[a].get() {
+                FsEntry::Dir { name } => Some(name.as_str()),
+                FsEntry::File { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        // The root is already the leading slash
+        names.pop();
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
 }
 
 #[cfg(test)]
@@ -154,4 +182,17 @@ mod test {
     fn test_part2() {
         assert_eq!(Day7.part2(TEST_INPUT), Some(24_933_642));
     }
+
+    #[test]
+    fn test_directory_sizes() {
+        assert_eq!(
+            directory_sizes(TEST_INPUT),
+            [
+                ("/".to_string(), 48_381_165),
+                ("/a".to_string(), 94_853),
+                ("/d".to_string(), 24_933_642),
+                ("/a/e".to_string(), 584),
+            ]
+        );
+    }
 }
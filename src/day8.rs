@@ -47,7 +47,7 @@ fn parse_trees(input: &str) -> Vec<Vec<u32>> {
         .collect::<Vec<_>>()
 }
 
-fn check_suitable(row_idx: usize, col_idx: usize, trees: &Vec<Vec<u32>>) -> (bool, usize) {
+fn check_suitable(row_idx: usize, col_idx: usize, trees: &[Vec<u32>]) -> (bool, usize) {
     if row_idx == 0
         || row_idx == trees.len() - 1
         || col_idx == 0
@@ -69,7 +69,7 @@ fn check_suitable(row_idx: usize, col_idx: usize, trees: &Vec<Vec<u32>>) -> (boo
         }
     }
 
-    for row in trees[row_idx + 1..].iter() {
+    for row in &trees[row_idx + 1..] {
         scenic_scores[1] += 1;
         if row[col_idx] >= height {
             visible_sides -= 1;
@@ -85,7 +85,7 @@ fn check_suitable(row_idx: usize, col_idx: usize, trees: &Vec<Vec<u32>>) -> (boo
         }
     }
 
-    for &tree in trees[row_idx][col_idx + 1..].iter() {
+    for &tree in &trees[row_idx][col_idx + 1..] {
         scenic_scores[3] += 1;
         if tree >= height {
             visible_sides -= 1;
@@ -0,0 +1,129 @@
+//! C ABI around [`registry::solve`], declared in `include/aoc_2022.h`. Building with the `ffi`
+//! feature generates the header into `OUT_DIR` with cbindgen, and `tests/ffi.rs` fails with the
+//! path to copy it from when the committed one is out of date.
+//!
+//! ```text
+//! cargo rustc --lib --release --no-default-features --features ffi --crate-type staticlib
+//! cc app.c -Iinclude -Ltarget/release -laoc_2022 -lpthread -ldl -lm
+//! ```
+//!
+//! Use `--crate-type cdylib` for a shared library instead.
+
+use crate::registry::{self, SolveError};
+use crate::Part;
+use std::slice;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AocStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    UnknownDay = 3,
+    UnknownPart = 4,
+    NotSolved = 5,
+    /// The solution panicked, usually because the input is malformed.
+    Panicked = 6,
+    /// The answer is longer than the buffer, `out_len` holds the length needed.
+    BufferTooSmall = 7,
+}
+
+/// Solves one part of a day and copies the answer into `out` as UTF-8, without a terminating NUL.
+///
+/// `*out_len` is the capacity of `out` on entry and the length of the answer on return. Nothing
+/// is written to `out` unless the result is `AOC_STATUS_OK`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, `out_len` to a writable `size_t` and `out` to
+/// `*out_len` writable bytes. Either pointer to bytes may be null if its length is zero.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> AocStatus {
+    if out_len.is_null() || (input.is_null() && input_len > 0) {
+        return AocStatus::NullPointer;
+    }
+    let input = if input_len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AocStatus::InvalidUtf8;
+    };
+    let Ok(part) = Part::try_from(part) else {
+        return AocStatus::UnknownPart;
+    };
+
+    let answer = match registry::solve(day, part, input) {
+        Ok(answer) => answer,
+        Err(SolveError::UnknownDay(_)) => return AocStatus::UnknownDay,
+        Err(SolveError::NotSolved(..)) => return AocStatus::NotSolved,
+        Err(SolveError::Panicked(..)) => return AocStatus::Panicked,
+    };
+    let capacity = *out_len;
+    *out_len = answer.len();
+    if answer.len() > capacity {
+        return AocStatus::BufferTooSmall;
+    }
+    if out.is_null() && !answer.is_empty() {
+        return AocStatus::NullPointer;
+    }
+    if !answer.is_empty() {
+        out.copy_from_nonoverlapping(answer.as_ptr(), answer.len());
+    }
+    AocStatus::Ok
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ptr;
+
+    fn solve(day: u8, part: u8, input: &[u8], capacity: usize) -> (AocStatus, String, usize) {
+        let mut out = vec![0; capacity];
+        let mut out_len = capacity;
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                &raw mut out_len,
+            )
+        };
+        let written = String::from_utf8_lossy(&out[..out_len.min(capacity)]).to_string();
+        (status, written, out_len)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = b"1000\n2000\n\n4000\n";
+        assert_eq!(
+            solve(1, 1, input, 16),
+            (AocStatus::Ok, "4000".to_string(), 4)
+        );
+        assert_eq!(solve(1, 2, input, 2).0, AocStatus::BufferTooSmall);
+        assert_eq!(solve(1, 2, input, 2).2, 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(solve(26, 1, b"", 16).0, AocStatus::UnknownDay);
+        assert_eq!(solve(1, 3, b"", 16).0, AocStatus::UnknownPart);
+        assert_eq!(solve(25, 2, b"1=\n", 16).0, AocStatus::NotSolved);
+        assert_eq!(solve(16, 1, b"not a valve", 16).0, AocStatus::Panicked);
+        assert_eq!(solve(1, 1, b"\xff", 16).0, AocStatus::InvalidUtf8);
+
+        let mut out_len = 0;
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 3, ptr::null_mut(), &raw mut out_len) };
+        assert_eq!(status, AocStatus::NullPointer);
+    }
+}
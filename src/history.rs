@@ -0,0 +1,209 @@
+//! Benchmark history kept as JSON Lines, one [`Record`] per day and part of every recorded run.
+
+use crate::{Part, Timings};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Relative to the current directory.
+pub const DEFAULT_FILE: &str = "bench-history.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// `git describe --always --dirty` of the checkout that was measured.
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub runs: u32,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Record {
+    #[must_use]
+    pub fn new(commit: &str, timestamp: u64, day: u8, part: Part, timings: &Timings) -> Self {
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        Self {
+            commit: commit.to_string(),
+            timestamp,
+            day,
+            part: u8::from(part),
+            runs: u32::try_from(timings.runs().len()).unwrap_or(u32::MAX),
+            min_ns: nanos(timings.min()),
+            median_ns: nanos(timings.median()),
+            max_ns: nanos(timings.max()),
+        }
+    }
+}
+
+/// Describes the checkout of the current directory, or `unknown` outside of a git repository.
+#[must_use]
+pub fn git_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map_or_else(|| "unknown".to_string(), |commit| commit.trim().to_string())
+}
+
+/// Appends `records` to the history at `path`, creating it if needed.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written.
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Reads every record of the history at `path`, in the order they were recorded.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or a line is not a record.
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                let message = format!("{}:{}: {e}", path.display(), index + 1);
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            })
+        })
+        .collect()
+}
+
+/// Medians of one part of a day across the history, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trend {
+    pub day: u8,
+    pub part: u8,
+    pub medians: Vec<u64>,
+}
+
+impl Trend {
+    /// How much slower the latest median is than the one before, as a fraction.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn change(&self) -> Option<f64> {
+        let [.., previous, latest] = self.medians[..] else {
+            return None;
+        };
+        Some(latest as f64 / previous.max(1) as f64 - 1.0)
+    }
+
+    /// Whether the latest median is more than `threshold` slower than the one before.
+    #[must_use]
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Groups the history into one trend per day and part, ordered by day and part.
+#[must_use]
+pub fn trends(records: &[Record]) -> Vec<Trend> {
+    let mut medians = BTreeMap::<(u8, u8), Vec<(u64, u64)>>::new();
+    for record in records {
+        medians
+            .entry((record.day, record.part))
+            .or_default()
+            .push((record.timestamp, record.median_ns));
+    }
+    medians
+        .into_iter()
+        .map(|((day, part), mut history)| {
+            // Stable, so records of the same second keep the order they were appended in
+            history.sort_by_key(|&(timestamp, _)| timestamp);
+            Trend {
+                day,
+                part,
+                medians: history.into_iter().map(|(_, median)| median).collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(timestamp: u64, day: u8, part: u8, median_ns: u64) -> Record {
+        Record {
+            commit: "abc1234".to_string(),
+            timestamp,
+            day,
+            part,
+            runs: 10,
+            min_ns: median_ns / 2,
+            median_ns,
+            max_ns: median_ns * 2,
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc_2022-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let records = [record(1, 1, 1, 100), record(1, 1, 2, 200)];
+        append(&path, &records[..1]).unwrap();
+        append(&path, &records[1..]).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+
+        fs::write(&path, "{\"commit\": 1}\n").unwrap();
+        let error = load(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains(".jsonl:1:"));
+    }
+
+    #[test]
+    fn test_trends() {
+        let records = [
+            record(2, 2, 1, 130),
+            record(1, 1, 1, 100),
+            record(1, 2, 1, 100),
+            record(2, 1, 1, 105),
+        ];
+        let trends = trends(&records);
+        assert_eq!(
+            trends,
+            [
+                Trend {
+                    day: 1,
+                    part: 1,
+                    medians: vec![100, 105]
+                },
+                Trend {
+                    day: 2,
+                    part: 1,
+                    medians: vec![100, 130]
+                },
+            ]
+        );
+        assert!(!trends[0].regressed(0.1));
+        assert!(trends[1].regressed(0.1));
+
+        let single = Trend {
+            day: 3,
+            part: 1,
+            medians: vec![100],
+        };
+        assert_eq!(single.change(), None);
+        assert!(!single.regressed(0.0));
+    }
+}
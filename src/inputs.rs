@@ -0,0 +1,52 @@
+//! Puzzle inputs, read at runtime from a directory holding one `dayNN.txt` per day.
+//!
+//! The binary defaults to [`DEFAULT_DIR`] under the current directory, so run it from the root
+//! of the checkout or pass `--inputs`. The benches embed `inputs/` of the checkout when they are
+//! compiled instead.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The `inputs/` directory, relative to the current directory.
+pub const DEFAULT_DIR: &str = "inputs";
+
+#[must_use]
+pub fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day:02}.txt"))
+}
+
+/// Reads the puzzle input for a day from `dir/dayNN.txt`, with Unix line endings.
+///
+/// # Errors
+///
+/// Returns an error if the file is missing or unreadable.
+pub fn read(dir: &Path, day: u8) -> io::Result<String> {
+    let input = fs::read_to_string(path(dir, day))?;
+    Ok(match normalise(&input) {
+        Cow::Borrowed(_) => input,
+        Cow::Owned(normalised) => normalised,
+    })
+}
+
+/// Turns Windows line endings into Unix ones, which is what the solutions expect.
+#[must_use]
+pub fn normalise(input: &str) -> Cow<'_, str> {
+    if input.contains("\r\n") {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert!(matches!(normalise("1\n2\n"), Cow::Borrowed("1\n2\n")));
+        assert_eq!(normalise("1\r\n\r\n2\r\n"), "1\n\n2\n");
+    }
+}
@@ -0,0 +1,321 @@
+//! Inclusive intervals of any integer type and sets of them.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::RangeInclusive;
+
+pub trait Integer: Copy + Ord + Debug {
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// Number of integers in `start..=end`, saturating at `u128::MAX`.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn checked_succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn count(start: Self, end: Self) -> u128 {
+                u128::try_from(end.abs_diff(start)).map_or(u128::MAX, |d| d.saturating_add(1))
+            }
+        }
+    )*};
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The integers from `start` to `end`, both included. Never empty.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// `None` if `start` is after `end`.
+    #[must_use]
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    #[must_use]
+    pub fn single(value: T) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    #[must_use]
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    #[must_use]
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of integers in the interval.
+    #[must_use]
+    pub fn size(&self) -> u128 {
+        T::count(self.start, self.end)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    #[must_use]
+    pub fn fully_contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && self.end >= other.start
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> IntervalSet<T> {
+        [*self, *other].into_iter().collect()
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> IntervalSet<T> {
+        IntervalSet::from(*self).difference(&IntervalSet::from(*other))
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl<T> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+/// Disjoint intervals, merged and sorted so that no two of them overlap or touch.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    #[must_use]
+    pub fn coverage(&self) -> u128 {
+        self.intervals
+            .iter()
+            .fold(0, |total, interval| total.saturating_add(interval.size()))
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while i < a.len() && j < b.len() {
+            intervals.extend(a[i].intersection(&b[j]));
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for interval in &self.intervals {
+            while j < other.intervals.len() && other.intervals[j].end < interval.start {
+                j += 1;
+            }
+            // Start of the part of `interval` not removed yet
+            let mut start = Some(interval.start);
+            for removed in other.intervals[j..]
+                .iter()
+                .take_while(|removed| removed.start <= interval.end)
+            {
+                let Some(from) = start else { break };
+                if removed.start > from {
+                    intervals.extend(
+                        removed
+                            .start
+                            .checked_pred()
+                            .and_then(|to| Interval::new(from, to)),
+                    );
+                }
+                start = removed
+                    .end
+                    .checked_succ()
+                    .filter(|&next| next <= interval.end);
+            }
+            intervals.extend(start.and_then(|from| Interval::new(from, interval.end)));
+        }
+        Self { intervals }
+    }
+
+    /// The parts of `bounds` that are not in the set.
+    #[must_use]
+    pub fn gaps(&self, bounds: Interval<T>) -> Self {
+        Self::from(bounds).difference(self)
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    /// Merges the intervals in O(n log n).
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted = iter.into_iter().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                // Touching intervals merge too, and nothing comes after an interval ending at MAX
+                Some(last)
+                    if last
+                        .end
+                        .checked_succ()
+                        .is_none_or(|next| interval.start <= next) =>
+                {
+                    last.end = last.end.max(interval.end);
+                }
+                _ => intervals.push(interval),
+            }
+        }
+        Self { intervals }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn interval<T: Integer>(start: T, end: T) -> Interval<T> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set<T: Integer>(intervals: &[(T, T)]) -> IntervalSet<T> {
+        intervals.iter().map(|&(s, e)| interval(s, e)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = interval(2u8, 6);
+        let b = interval(4u8, 8);
+        assert!(Interval::new(3, 2).is_none());
+        assert_eq!(a.size(), 5);
+        assert_eq!(a.intersection(&b), Some(interval(4, 6)));
+        assert_eq!(a.intersection(&interval(7, 9)), None);
+        assert_eq!(a.union(&b), set(&[(2, 8)]));
+        assert_eq!(a.union(&interval(7, 9)), set(&[(2, 9)]));
+        assert_eq!(a.difference(&b), set(&[(2, 3)]));
+        assert_eq!(a.difference(&interval(3, 4)), set(&[(2, 2), (5, 6)]));
+        assert!(a.difference(&interval(0, 200)).is_empty());
+        assert!(interval(2, 8).fully_contains(&interval(3, 7)));
+        assert!(!a.overlaps(&interval(7, 7)));
+        assert_eq!(a.to_string(), "2-6");
+        assert_eq!(RangeInclusive::from(a), 2..=6);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let merged = set(&[(5, 7), (1, 2), (3, 3), (10, 12), (11, 20)]);
+        assert_eq!(merged, set(&[(1, 3), (5, 7), (10, 20)]));
+        assert_eq!(merged.intervals().len(), 3);
+        assert_eq!(merged.coverage(), 3 + 3 + 11);
+        assert!(merged.contains(6) && !merged.contains(4) && !merged.contains(21));
+
+        let other = set(&[(2, 6), (15, 30)]);
+        assert_eq!(merged.union(&other), set(&[(1, 7), (10, 30)]));
+        assert_eq!(
+            merged.intersection(&other),
+            set(&[(2, 3), (5, 6), (15, 20)])
+        );
+        assert_eq!(merged.difference(&other), set(&[(1, 1), (7, 7), (10, 14)]));
+        assert_eq!(
+            merged.gaps(interval(0, 22)),
+            set(&[(0, 0), (4, 4), (8, 9), (21, 22)])
+        );
+    }
+
+    #[test]
+    fn test_extremes() {
+        let all = set(&[(i8::MIN, -1), (0, i8::MAX)]);
+        assert_eq!(all.intervals(), [interval(i8::MIN, i8::MAX)]);
+        assert_eq!(all.coverage(), 256);
+        assert!(all.difference(&set(&[(i8::MIN, i8::MAX)])).is_empty());
+        assert_eq!(
+            all.difference(&set(&[(i8::MIN, 0), (100, i8::MAX)])),
+            set(&[(1, 99)])
+        );
+        assert_eq!(set(&[(0, u128::MAX)]).coverage(), u128::MAX);
+        assert_eq!(set(&[(0u64, 1), (u64::MAX, u64::MAX)]).intervals().len(), 2);
+    }
+}
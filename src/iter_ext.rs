@@ -0,0 +1,102 @@
+//! Stable replacements for the nightly `Iterator::array_chunks` and `<[T]>::array_windows`.
+//!
+//! With the `nightly` feature enabled these forward to the standard library versions.
+
+pub(crate) trait ArrayChunkedExt: Iterator + Sized {
+    /// Yields non-overlapping arrays of `N` items, dropping a trailing partial chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0, like the standard library version.
+    fn array_chunked<const N: usize>(self) -> impl Iterator<Item = [Self::Item; N]>;
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<I: Iterator> ArrayChunkedExt for I {
+    fn array_chunked<const N: usize>(self) -> impl Iterator<Item = [Self::Item; N]> {
+        assert!(N != 0, "chunk size must be non-zero");
+        ArrayChunked::<I, N> { iter: self }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<I: Iterator> ArrayChunkedExt for I {
+    fn array_chunked<const N: usize>(self) -> impl Iterator<Item = [Self::Item; N]> {
+        self.array_chunks::<N>()
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+struct ArrayChunked<I, const N: usize> {
+    iter: I,
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<I: Iterator, const N: usize> Iterator for ArrayChunked<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let items = [(); N].map(|()| self.iter.next());
+        if items.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(items.map(Option::unwrap))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / N, upper.map(|u| u / N))
+    }
+}
+
+pub(crate) trait ArrayWindowedExt<T> {
+    /// Yields every overlapping window of `N` elements as an array reference.
+    fn array_windowed<'a, const N: usize>(&'a self) -> impl Iterator<Item = &'a [T; N]>
+    where
+        T: 'a;
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<T> ArrayWindowedExt<T> for [T] {
+    fn array_windowed<'a, const N: usize>(&'a self) -> impl Iterator<Item = &'a [T; N]>
+    where
+        T: 'a,
+    {
+        self.windows(N)
+            .map(|window| window.try_into().expect("Window has length N"))
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T> ArrayWindowedExt<T> for [T] {
+    fn array_windowed<'a, const N: usize>(&'a self) -> impl Iterator<Item = &'a [T; N]>
+    where
+        T: 'a,
+    {
+        self.array_windows::<N>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_array_chunked() {
+        let chunks = (1..=7).array_chunked::<3>().collect::<Vec<_>>();
+        assert_eq!(chunks, vec![[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_array_chunked_empty() {
+        let _ = (1..=7).array_chunked::<0>();
+    }
+
+    #[test]
+    fn test_array_windowed() {
+        let windows = [1, 2, 3, 4].array_windowed::<2>().collect::<Vec<_>>();
+        assert_eq!(windows, vec![&[1, 2], &[2, 3], &[3, 4]]);
+        assert_eq!([1, 2].array_windowed::<3>().count(), 0);
+    }
+}
@@ -0,0 +1,195 @@
+//! Local record of submitted answers, used to avoid sending an answer that is already known to
+//! be wrong.
+
+use crate::Part;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Kept in the inputs directory next to the puzzle inputs.
+pub const DEFAULT_FILE: &str = ".submissions.json";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Submitted too soon after the previous answer, so the answer was not checked.
+    Wait,
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    /// The response page didn't match any known message, so nothing is known about the answer.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait => "not checked, submitted too recently",
+            Verdict::WrongLevel => "not checked, part already solved or locked",
+            Verdict::Unknown => "unknown, the response was not recognised",
+        };
+        write!(f, "{text}")
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Submission {
+    /// Whether this earlier submission already tells us `answer` can't be correct.
+    fn rules_out(&self, answer: &str) -> bool {
+        let ordering = || {
+            let previous = self.answer.parse::<i128>().ok()?;
+            Some(answer.parse::<i128>().ok()?.cmp(&previous))
+        };
+        match self.verdict {
+            Verdict::Correct => true,
+            Verdict::Wrong => self.answer == answer,
+            Verdict::TooHigh => self.answer == answer || ordering() == Some(Ordering::Greater),
+            Verdict::TooLow => self.answer == answer || ordering() == Some(Ordering::Less),
+            Verdict::Wait | Verdict::WrongLevel | Verdict::Unknown => false,
+        }
+    }
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, starting an empty one if the file doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be read or parsed.
+    pub fn open(path: &Path) -> io::Result<Ledger> {
+        let submissions = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Ledger {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    #[must_use]
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// Finds an earlier submission showing that `answer` is not worth sending.
+    #[must_use]
+    pub fn conflict(&self, day: u8, part: Part, answer: &str) -> Option<&Submission> {
+        self.submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == u8::from(part))
+            .find(|s| s.rules_out(answer))
+    }
+
+    /// Appends a submission and writes the ledger back to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger can't be written.
+    pub fn record(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &str,
+        verdict: Verdict,
+    ) -> io::Result<()> {
+        self.submissions.push(Submission {
+            day,
+            part: u8::from(part),
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        });
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.submissions)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ledger(name: &str) -> Ledger {
+        let path = std::env::temp_dir().join(format!(
+            "aoc_2022-ledger-{name}-{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        Ledger::open(&path).unwrap()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut ledger = ledger("bounds");
+        ledger
+            .record(1, Part::One, "100", Verdict::TooHigh)
+            .unwrap();
+        ledger.record(1, Part::One, "50", Verdict::TooLow).unwrap();
+        ledger.record(1, Part::One, "75", Verdict::Wrong).unwrap();
+
+        assert!(ledger.conflict(1, Part::One, "100").is_some());
+        assert!(ledger.conflict(1, Part::One, "150").is_some());
+        assert!(ledger.conflict(1, Part::One, "20").is_some());
+        assert!(ledger.conflict(1, Part::One, "75").is_some());
+        assert!(ledger.conflict(1, Part::One, "74").is_none());
+        assert!(ledger.conflict(1, Part::Two, "150").is_none());
+        assert!(ledger.conflict(2, Part::One, "150").is_none());
+    }
+
+    #[test]
+    fn test_unchecked_answers_can_be_resent() {
+        let mut ledger = ledger("unchecked");
+        ledger.record(5, Part::Two, "MCD", Verdict::Wait).unwrap();
+        ledger
+            .record(5, Part::Two, "MCD", Verdict::Unknown)
+            .unwrap();
+        assert!(ledger.conflict(5, Part::Two, "MCD").is_none());
+
+        ledger
+            .record(5, Part::Two, "MCD", Verdict::Correct)
+            .unwrap();
+        assert!(ledger.conflict(5, Part::Two, "CMZ").is_some());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut ledger = ledger("round-trip");
+        ledger
+            .record(3, Part::One, "157", Verdict::Correct)
+            .unwrap();
+        let reopened = Ledger::open(&ledger.path).unwrap();
+        assert_eq!(reopened.submissions(), ledger.submissions());
+    }
+}
@@ -1,10 +1,14 @@
-#![feature(iter_array_chunks, array_windows)]
-#![feature(assert_matches)]
-#![feature(array_zip)]
+#![cfg_attr(feature = "nightly", feature(iter_array_chunks, array_windows))]
+#![cfg_attr(feature = "nightly", allow(stable_features))]
 #![deny(clippy::all)]
 #![warn(clippy::pedantic)]
 
-use std::fmt::Display;
+use registry::{DynSolution, Variant};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 pub trait Solution<const DAY: u8> {
     type Output: Display;
@@ -16,34 +20,227 @@ pub trait Solution<const DAY: u8> {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Part must be 1 or 2, got {s}")),
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("Part must be 1 or 2, got {value}")),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+/// Durations of repeated runs of a single part, sorted from fastest to slowest.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    runs: Vec<Duration>,
+}
+
+impl Timings {
+    fn new(mut runs: Vec<Duration>) -> Self {
+        runs.sort_unstable();
+        Self { runs }
+    }
+
+    #[must_use]
+    pub fn runs(&self) -> &[Duration] {
+        &self.runs
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Duration {
+        self.runs[0]
+    }
+
+    #[must_use]
+    pub fn median(&self) -> Duration {
+        self.runs[self.runs.len() / 2]
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Duration {
+        self.runs[self.runs.len() - 1]
+    }
+}
+
 pub struct Runner<'a> {
-    input: &'a str,
+    input: Cow<'a, str>,
 }
 
 impl Runner<'_> {
     #[must_use]
     pub fn new(input: &'_ str) -> Runner<'_> {
-        Runner { input }
+        Runner {
+            input: inputs::normalise(input),
+        }
     }
 
-    pub fn run<const DAY: u8, S: Solution<DAY>>(&'_ self, solution: &S) {
-        let input = &self.input.replace("\r\n", "\n");
+    pub fn run(&'_ self, solution: &dyn DynSolution) {
+        let input = &*self.input;
         println!("--------");
-        println!("Day {DAY:02}");
+        println!("Day {:02}", solution.day());
         println!("--------");
 
-        let result = solution.part1(input);
+        let (result, part1_stats) = measure(|| solution.part1(input));
         println!("Part 1:\n{result}");
 
-        let result = solution.part2(input);
+        let (result, part2_stats) = measure(|| solution.part2(input));
         if let Some(result) = result {
             println!("Part 2:\n{result}");
         } else {
             println!("Part 2:\nNot solved");
         }
+
+        #[cfg(feature = "memory-stats")]
+        {
+            println!("--------");
+            println!("Memory, each part parsing its own input:");
+            println!("Part 1: {part1_stats}");
+            println!("Part 2: {part2_stats}");
+        }
+        #[cfg(not(feature = "memory-stats"))]
+        let _ = (part1_stats, part2_stats);
+    }
+
+    /// Runs one part `times` times on the same input and collects how long each run took.
+    ///
+    /// Returns `None` if the part is not solved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` is zero.
+    pub fn time(&'_ self, solution: &dyn DynSolution, part: Part, times: u32) -> Option<Timings> {
+        assert!(times > 0, "Need at least one run to time");
+        let input = &*self.input;
+        let mut runs = Vec::with_capacity(times as usize);
+        for _ in 0..times {
+            let start = Instant::now();
+            let solved = match part {
+                Part::One => {
+                    black_box(solution.part1(black_box(input)));
+                    true
+                }
+                Part::Two => black_box(solution.part2(black_box(input))).is_some(),
+            };
+            runs.push(start.elapsed());
+            if !solved {
+                return None;
+            }
+        }
+        Some(Timings::new(runs))
+    }
+
+    /// Solves one part without printing anything. Returns `None` if the part is not solved.
+    #[must_use]
+    pub fn answer(&'_ self, solution: &dyn DynSolution, part: Part) -> Option<String> {
+        let input = &*self.input;
+        match part {
+            Part::One => Some(solution.part1(input)),
+            Part::Two => solution.part2(input),
+        }
+    }
+
+    pub fn repeat(&'_ self, solution: &dyn DynSolution, part: Part, times: u32) {
+        println!("--------");
+        println!("Day {:02} Part {part} x{times}", solution.day());
+        println!("--------");
+
+        if let Some(timings) = self.time(solution, part, times) {
+            println!("Min:    {:?}", timings.min());
+            println!("Median: {:?}", timings.median());
+            println!("Max:    {:?}", timings.max());
+        } else {
+            println!("Not solved");
+        }
+    }
+
+    /// Runs every variant of a day on the same input and prints the median time of each part.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variants disagree on an answer.
+    pub fn compare(&'_ self, variants: &[Variant], times: u32) {
+        let Some(reference) = variants.first() else {
+            return;
+        };
+        let input = &*self.input;
+        println!("--------");
+        println!("Day {:02} x{times}", reference.solution.day());
+        println!("--------");
+        println!("{:<16}{:>16}{:>16}", "Variant", "Part 1", "Part 2");
+
+        let mut answers = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let median = |part| {
+                self.time(variant.solution, part, times)
+                    .map_or_else(|| "-".to_string(), |t| format!("{:?}", t.median()))
+            };
+            println!(
+                "{:<16}{:>16}{:>16}",
+                variant.name,
+                median(Part::One),
+                median(Part::Two)
+            );
+            answers.push((variant.solution.part1(input), variant.solution.part2(input)));
+        }
+
+        for (variant, answer) in variants.iter().zip(&answers).skip(1) {
+            assert_eq!(
+                answer, &answers[0],
+                "Variant {} disagrees with {}",
+                variant.name, reference.name
+            );
+        }
+        println!("--------");
+        println!("All variants agree");
     }
 }
 
+#[cfg(feature = "memory-stats")]
+use memory::measure;
+
+#[cfg(not(feature = "memory-stats"))]
+fn measure<T>(f: impl FnOnce() -> T) -> (T, ()) {
+    (f(), ())
+}
+
+#[cfg(feature = "client")]
+pub mod client;
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -69,3 +266,23 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod inputs;
+pub mod interval;
+mod iter_ext;
+#[cfg(feature = "client")]
+pub mod ledger;
+#[cfg(feature = "memory-stats")]
+pub mod memory;
+pub mod puzzle;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod registry;
+pub mod repl;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "wasm")]
+pub mod wasm;
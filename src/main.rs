@@ -1,42 +1,406 @@
 use aoc_2022::*;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
+use std::path::{Path, PathBuf};
 
-#[derive(Parser, Debug)]
+#[cfg(feature = "memory-stats")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+/// Advent of Code 2022 solutions
+#[derive(clap::Parser, Debug)]
 #[clap(long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Directory holding the dayNN.txt puzzle inputs, read when a command runs
+    #[arg(long, global = true, default_value = inputs::DEFAULT_DIR)]
+    inputs: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
     #[arg(default_value_t = 1)]
     day: u8,
+    /// Run a single part this many times and print its timings instead of the answers
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: Option<u32>,
+    /// Part to run with --repeat
+    #[arg(long, default_value = "1", requires = "repeat")]
+    part: Part,
+    /// Day 1 only: list the k elves carrying the most calories instead of the answers
+    #[arg(long, value_name = "K", conflicts_with = "repeat")]
+    top: Option<usize>,
+    /// Count malformed lines as 0 calories with --top instead of failing
+    #[arg(long, requires = "top")]
+    lenient: bool,
+    /// Day 5 only: rearrange the crates with this crane and draw the result instead of the
+    /// answers. One of 9000, 9001, batch:<size>, capacity:<crates> or rotate:<crates>
+    #[arg(long, conflicts_with_all = ["repeat", "top"], value_parser = crane_name)]
+    crane: Option<String>,
+}
+
+fn crane_name(name: &str) -> Result<String, String> {
+    day5::parse_crane(name).map(|_| name.to_string())
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Time every day that has an input and append the results to the benchmark history
+    #[cfg(feature = "history")]
+    BenchRecord {
+        /// Number of runs to take the median of
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: u32,
+        #[arg(long, default_value = history::DEFAULT_FILE)]
+        history: PathBuf,
+    },
+    /// Show how the median of every day changed across the benchmark history
+    #[cfg(feature = "history")]
+    BenchReport {
+        /// Flag days whose latest median is this many percent slower than the one before
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        #[arg(long, default_value = history::DEFAULT_FILE)]
+        history: PathBuf,
+    },
+    /// Run every implementation of a day, check that they agree and compare their timings
+    Compare {
+        day: u8,
+        /// Number of runs to take the median of
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: u32,
+    },
+    /// Print a shell completion script
+    Completions { shell: clap_complete::Shell },
+    /// Download a puzzle input into the inputs directory unless it is already there
+    #[cfg(feature = "client")]
+    Fetch {
+        day: u8,
+        #[arg(long, default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// File holding the session cookie, used when AOC_SESSION is not set
+        #[arg(long, default_value = client::DEFAULT_SESSION_FILE)]
+        session_file: PathBuf,
+        /// Minimum number of seconds between requests to the server
+        #[arg(long, default_value_t = client::DEFAULT_MIN_INTERVAL.as_secs())]
+        min_interval: u64,
+    },
+    /// Show every puzzle with its tags and the algorithm used
+    List {
+        /// Only show puzzles with this tag
+        #[arg(long)]
+        tag: Option<puzzle::Tag>,
+    },
+    /// Print a man page in roff format
+    Manpage,
+    /// Open a shell to load inputs, change parameters and rerun parts without restarting
+    Repl,
+    /// Solve a part and submit the answer, unless earlier submissions already rule it out
+    #[cfg(feature = "client")]
+    Submit {
+        day: u8,
+        part: Part,
+        #[arg(long, default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// File holding the session cookie, used when AOC_SESSION is not set
+        #[arg(long, default_value = client::DEFAULT_SESSION_FILE)]
+        session_file: PathBuf,
+        /// Minimum number of seconds between requests to the server
+        #[arg(long, default_value_t = client::DEFAULT_MIN_INTERVAL.as_secs())]
+        min_interval: u64,
+        /// Record of earlier submissions [default: <inputs>/.submissions.json]
+        #[arg(long)]
+        ledger: Option<PathBuf>,
+    },
+    /// Answer POST /solve/{day}/{part} requests on localhost
+    #[cfg(feature = "serve")]
+    Serve {
+        #[arg(long, default_value_t = 8022)]
+        port: u16,
+    },
+}
+
+/// The clap command of [`Args`], with the list of days added to the help.
+fn cli() -> clap::Command {
+    let days = registry::solutions()
+        .iter()
+        .map(|solution| {
+            let day = solution.day();
+            let title = puzzle::puzzle(day).map_or("", |puzzle| puzzle.title);
+            format!("  {day:>2}  {title}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Args::command().after_help(format!("Days:\n{days}"))
 }
 
 fn main() {
-    let day = Args::parse().day;
-
-    match day {
-        1 => Runner::new(include_str!("../inputs/day01.txt")).run(&day1::Day1),
-        2 => Runner::new(include_str!("../inputs/day02.txt")).run(&day2::Day2),
-        3 => Runner::new(include_str!("../inputs/day03.txt")).run(&day3::Day3),
-        4 => Runner::new(include_str!("../inputs/day04.txt")).run(&day4::Day4),
-        5 => Runner::new(include_str!("../inputs/day05.txt")).run(&day5::Day5),
-        6 => Runner::new(include_str!("../inputs/day06.txt")).run(&day6::Day6),
-        7 => Runner::new(include_str!("../inputs/day07.txt")).run(&day7::Day7),
-        8 => Runner::new(include_str!("../inputs/day08.txt")).run(&day8::Day8),
-        9 => Runner::new(include_str!("../inputs/day09.txt")).run(&day9::Day9),
-        10 => Runner::new(include_str!("../inputs/day10.txt")).run(&day10::Day10),
-        11 => Runner::new(include_str!("../inputs/day11.txt")).run(&day11::Day11),
-        12 => Runner::new(include_str!("../inputs/day12.txt")).run(&day12::Day12),
-        13 => Runner::new(include_str!("../inputs/day13.txt")).run(&day13::Day13),
-        14 => Runner::new(include_str!("../inputs/day14.txt")).run(&day14::Day14),
-        15 => Runner::new(include_str!("../inputs/day15.txt")).run(&day15::Day15::puzzle()),
-        16 => Runner::new(include_str!("../inputs/day16.txt")).run(&day16::Day16),
-        17 => Runner::new(include_str!("../inputs/day17.txt")).run(&day17::Day17),
-        18 => Runner::new(include_str!("../inputs/day18.txt")).run(&day18::Day18),
-        19 => Runner::new(include_str!("../inputs/day19.txt")).run(&day19::Day19),
-        20 => Runner::new(include_str!("../inputs/day20.txt")).run(&day20::Day20),
-        21 => Runner::new(include_str!("../inputs/day21.txt")).run(&day21::Day21),
-        22 => Runner::new(include_str!("../inputs/day22.txt")).run(&day22::Day22),
-        23 => Runner::new(include_str!("../inputs/day23.txt")).run(&day23::Day23),
-        24 => Runner::new(include_str!("../inputs/day24.txt")).run(&day24::Day24),
-        25 => Runner::new(include_str!("../inputs/day25.txt")).run(&day25::Day25),
-        _ => panic!("Could not find day {day}"),
+    let args = Args::from_arg_matches(&cli().get_matches()).unwrap_or_else(|e| e.exit());
+
+    match args.command {
+        #[cfg(feature = "history")]
+        Some(Command::BenchRecord { repeat, history }) => {
+            bench_record(&args.inputs, repeat, &history);
+        }
+        #[cfg(feature = "history")]
+        Some(Command::BenchReport { threshold, history }) => bench_report(threshold, &history),
+        Some(Command::Compare { day, repeat }) => {
+            let variants = registry::variants(day);
+            if variants.is_empty() {
+                panic!("Could not find day {day}");
+            }
+            Runner::new(&input(&args.inputs, day)).compare(&variants, repeat);
+        }
+        #[cfg(feature = "client")]
+        Some(Command::Fetch {
+            day,
+            base_url,
+            session_file,
+            min_interval,
+        }) => {
+            let session = client::session_token(&session_file).unwrap_or_else(|e| panic!("{e}"));
+            let client = client::Client::new(&base_url, &session, &args.inputs)
+                .min_interval(std::time::Duration::from_secs(min_interval));
+            match client.fetch(day) {
+                Ok(client::Fetched::Cached(path)) => println!("Already have {}", path.display()),
+                Ok(client::Fetched::Downloaded(path)) => println!("Saved {}", path.display()),
+                Err(e) => panic!("Could not fetch day {day}: {e}"),
+            }
+        }
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut cli(), "aoc_2022", &mut std::io::stdout());
+        }
+        Some(Command::List { tag }) => list(tag),
+        Some(Command::Manpage) => {
+            clap_mangen::Man::new(cli())
+                .render(&mut std::io::stdout())
+                .unwrap_or_else(|e| panic!("Could not write the man page: {e}"));
+        }
+        Some(Command::Repl) => {
+            let stdin = std::io::stdin().lock();
+            repl::Repl::new(&args.inputs)
+                .run(stdin, std::io::stdout())
+                .unwrap_or_else(|e| panic!("Could not run the shell: {e}"));
+        }
+        #[cfg(feature = "client")]
+        Some(Command::Submit {
+            day,
+            part,
+            base_url,
+            session_file,
+            min_interval,
+            ledger,
+        }) => {
+            let solution =
+                registry::solution(day).unwrap_or_else(|| panic!("Could not find day {day}"));
+            let answer = Runner::new(&input(&args.inputs, day))
+                .answer(solution, part)
+                .unwrap_or_else(|| panic!("Day {day:02} part {part} is not solved"));
+
+            let ledger_path = ledger.unwrap_or_else(|| args.inputs.join(ledger::DEFAULT_FILE));
+            let mut ledger = ledger::Ledger::open(&ledger_path)
+                .unwrap_or_else(|e| panic!("Could not read {}: {e}", ledger_path.display()));
+            if let Some(earlier) = ledger.conflict(day, part, &answer) {
+                println!(
+                    "Not submitting {answer}, {} was {}",
+                    earlier.answer, earlier.verdict
+                );
+                return;
+            }
+
+            let session = client::session_token(&session_file).unwrap_or_else(|e| panic!("{e}"));
+            let client = client::Client::new(&base_url, &session, &args.inputs)
+                .min_interval(std::time::Duration::from_secs(min_interval));
+            let verdict = client
+                .submit(day, part, &answer)
+                .unwrap_or_else(|e| panic!("Could not submit day {day} part {part}: {e}"));
+            ledger
+                .record(day, part, &answer, verdict)
+                .unwrap_or_else(|e| panic!("Could not write {}: {e}", ledger_path.display()));
+            println!("{answer}: {verdict}");
+        }
+        #[cfg(feature = "serve")]
+        Some(Command::Serve { port }) => {
+            let server = serve::Server::bind(port)
+                .unwrap_or_else(|e| panic!("Could not listen on port {port}: {e}"));
+            println!("Listening on http://127.0.0.1:{}", server.port());
+            server.run();
+        }
+        None => run(&args.run, &args.inputs),
+    }
+}
+
+fn run(args: &RunArgs, inputs: &Path) {
+    let solution =
+        registry::solution(args.day).unwrap_or_else(|| panic!("Could not find day {}", args.day));
+
+    let input = input(inputs, args.day);
+    if let Some(k) = args.top {
+        assert!(args.day == 1, "--top only applies to day 1");
+        return top_elves(&input, k, args.lenient);
+    }
+    if let Some(crane) = &args.crane {
+        assert!(args.day == 5, "--crane only applies to day 5");
+        return rearrange(&input, crane);
+    }
+
+    let runner = Runner::new(&input);
+    match args.repeat {
+        Some(times) => runner.repeat(solution, args.part, times),
+        None => runner.run(solution),
     }
 }
+
+fn top_elves(input: &str, k: usize, lenient: bool) {
+    let parsing = if lenient {
+        day1::Parsing::Lenient
+    } else {
+        day1::Parsing::Strict
+    };
+    let top = day1::top_elves(input, k, parsing).unwrap_or_else(|e| panic!("{e}"));
+    for (rank, elf) in top.iter().enumerate() {
+        let items = elf
+            .items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        println!(
+            "{:>3}. elf {:<6}{:>8}  ({})",
+            rank + 1,
+            elf.index,
+            elf.total,
+            items.join(" + ")
+        );
+    }
+}
+
+fn rearrange(input: &str, crane: &str) {
+    let crane = day5::parse_crane(crane).unwrap_or_else(|e| panic!("{e}"));
+    let mut yard = day5::CrateYard::parse(input, crane).unwrap_or_else(|e| panic!("{e}"));
+    yard.seek(yard.moves().len()).unwrap_or_else(|e| {
+        let number = yard.position() + 1;
+        panic!("Move {number} ({}): {e}", yard.moves()[number - 1])
+    });
+    println!("{}", yard.render());
+    println!("Top crates: {}", yard.tops());
+}
+
+#[cfg(feature = "history")]
+fn bench_record(inputs: &Path, repeat: u32, path: &Path) {
+    let commit = history::git_commit();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut records = Vec::new();
+    for solution in registry::solutions() {
+        let day = solution.day();
+        let input = match inputs::read(inputs, day) {
+            Ok(input) if !input.trim().is_empty() => input,
+            _ => {
+                println!("Day {day:02}: no input, skipped");
+                continue;
+            }
+        };
+        let runner = Runner::new(&input);
+        // The panic message is printed by the panic hook, the other days are still recorded
+        let Ok(timings) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            [Part::One, Part::Two].map(|part| (part, runner.time(*solution, part, repeat)))
+        })) else {
+            println!("Day {day:02}: panicked, skipped");
+            continue;
+        };
+        for (part, timings) in timings {
+            if let Some(timings) = timings {
+                println!("Day {day:02} part {part}: {:?}", timings.median());
+                records.push(history::Record::new(
+                    &commit, timestamp, day, part, &timings,
+                ));
+            }
+        }
+    }
+
+    history::append(path, &records)
+        .unwrap_or_else(|e| panic!("Could not write {}: {e}", path.display()));
+    println!(
+        "Recorded {} results for {commit} in {}",
+        records.len(),
+        path.display()
+    );
+}
+
+#[cfg(feature = "history")]
+fn bench_report(threshold: f64, path: &Path) {
+    let records =
+        history::load(path).unwrap_or_else(|e| panic!("Could not read {}: {e}", path.display()));
+    let trends = history::trends(&records);
+    let duration = |nanos: u64| format!("{:?}", std::time::Duration::from_nanos(nanos));
+
+    println!(
+        "{:<5}{:<6}{:>6}{:>14}{:>14}{:>14}{:>10}",
+        "Day", "Part", "Runs", "First", "Previous", "Latest", "Change"
+    );
+    let mut regressions = 0;
+    for trend in &trends {
+        let [first, .., latest] = trend.medians[..] else {
+            continue;
+        };
+        let previous = trend.medians.len().checked_sub(2).map(|i| trend.medians[i]);
+        let change = trend.change().map_or_else(
+            || "-".to_string(),
+            |change| format!("{:+.1}%", change * 100.0),
+        );
+        let regressed = trend.regressed(threshold / 100.0);
+        regressions += usize::from(regressed);
+        println!(
+            "{:<5}{:<6}{:>6}{:>14}{:>14}{:>14}{:>10}{}",
+            trend.day,
+            trend.part,
+            trend.medians.len(),
+            duration(first),
+            previous.map_or_else(|| "-".to_string(), duration),
+            duration(latest),
+            change,
+            if regressed { "  REGRESSED" } else { "" }
+        );
+    }
+    println!("--------");
+    println!("{regressions} regressions above {threshold}%");
+}
+
+fn list(tag: Option<puzzle::Tag>) {
+    println!("{:<5}{:<26}{:<26}Algorithm", "Day", "Title", "Tags");
+    for puzzle in puzzle::puzzles() {
+        if tag.is_some_and(|tag| !puzzle.has_tag(tag)) {
+            continue;
+        }
+        let tags = puzzle
+            .tags
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        println!(
+            "{:<5}{:<26}{:<26}{}",
+            puzzle.day,
+            puzzle.title,
+            tags.join(", "),
+            puzzle.algorithm
+        );
+    }
+}
+
+fn input(inputs: &Path, day: u8) -> String {
+    inputs::read(inputs, day).unwrap_or_else(|e| {
+        let path = inputs::path(inputs, day);
+        let path = std::path::absolute(&path).unwrap_or(path);
+        panic!(
+            "Could not read {}: {e}. Pass the directory holding the inputs with --inputs, \
+             or download this one with `fetch {day}`",
+            path.display()
+        )
+    })
+}
@@ -0,0 +1,123 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and keeps running totals of allocated and live bytes.
+///
+/// The counters are only updated once this is installed as the `#[global_allocator]`.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::record_alloc(new_size - layout.size());
+            } else {
+                Self::record_dealloc(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AllocationStats {
+    /// Total bytes handed out while measuring, including memory that was freed again.
+    pub allocated: usize,
+    /// Highest number of live bytes on top of what was already live when measuring started.
+    pub peak_live: usize,
+}
+
+impl Display for AllocationStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes allocated, {} bytes peak",
+            self.allocated, self.peak_live
+        )
+    }
+}
+
+/// Runs `f` and reports the allocations it made.
+///
+/// Other threads allocating at the same time are counted as well, so measurements should not
+/// overlap.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    let live_before = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live_before, Ordering::Relaxed);
+    let allocated_before = ALLOCATED.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let peak_live = PEAK.load(Ordering::Relaxed).saturating_sub(live_before);
+    let allocated = ALLOCATED.load(Ordering::Relaxed) - allocated_before;
+    let stats = AllocationStats {
+        allocated,
+        peak_live,
+    };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        let (_, stats) = measure(|| {
+            let first = vec![0u8; 4096];
+            drop(first);
+            vec![0u8; 1024]
+        });
+        assert!(stats.allocated >= 4096 + 1024);
+
+        let (kept, stats) = measure(|| Vec::<u8>::with_capacity(1024));
+        assert_eq!(kept.capacity(), 1024);
+        assert!(stats.allocated >= 1024);
+
+        // Bigger than anything other tests free meanwhile, which would lower the live count
+        let (kept, stats) = measure(|| Vec::<u8>::with_capacity(16 << 20));
+        assert!(stats.peak_live >= kept.capacity());
+    }
+}
@@ -0,0 +1,183 @@
+//! What each day is about, for listing and grouping the solutions.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Tag {
+    Parsing,
+    Simulation,
+    Grid,
+    Graph,
+    Search,
+    Dp,
+    Math,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 7] = [
+        Tag::Parsing,
+        Tag::Simulation,
+        Tag::Grid,
+        Tag::Graph,
+        Tag::Search,
+        Tag::Dp,
+        Tag::Math,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Parsing => "parsing",
+            Tag::Simulation => "simulation",
+            Tag::Grid => "grid",
+            Tag::Graph => "graph",
+            Tag::Search => "search",
+            Tag::Dp => "dp",
+            Tag::Math => "math",
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::ALL
+            .into_iter()
+            .find(|tag| tag.name() == s)
+            .ok_or_else(|| {
+                let names = Tag::ALL.map(Tag::name).join(", ");
+                format!("Unknown tag {s}, expected one of {names}")
+            })
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    /// The first tag is the main one.
+    pub tags: &'static [Tag],
+    pub algorithm: &'static str,
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn has_tag(&self, tag: Tag) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+macro_rules! puzzles {
+    ($($day:literal => $title:literal, [$($tag:ident),+], $algorithm:literal;)*) => {
+        [$(Puzzle {
+            day: $day,
+            title: $title,
+            tags: &[$(Tag::$tag),+],
+            algorithm: $algorithm,
+        }),*]
+    };
+}
+
+static PUZZLES: [Puzzle; 25] = puzzles! {
+    1 => "Calorie Counting", [Parsing],
+        "Sum each group of lines, keep the largest totals";
+    2 => "Rock Paper Scissors", [Parsing, Math],
+        "Score each round from a table of which shape beats which";
+    3 => "Rucksack Reorganization", [Parsing],
+        "Intersect 52-bit item sets of the halves or of groups of rucksacks";
+    4 => "Camp Cleanup", [Parsing],
+        "Containment and overlap checks on generic integer intervals";
+    5 => "Supply Stacks", [Simulation, Parsing],
+        "Move crates between stacks one at a time or in blocks";
+    6 => "Tuning Trouble", [Parsing],
+        "Sliding window until all characters are distinct";
+    7 => "No Space Left On Device", [Parsing, Graph],
+        "Build the directory tree in an arena, sum sizes recursively";
+    8 => "Treetop Tree House", [Grid],
+        "Look in all four directions from every tree";
+    9 => "Rope Bridge", [Simulation, Grid],
+        "Every knot follows the one before it, tail positions go in a set";
+    10 => "Cathode-Ray Tube", [Simulation, Parsing],
+        "Replay the register value per cycle and draw the screen";
+    11 => "Monkey in the Middle", [Simulation, Math],
+        "Simulate the rounds with worry levels modulo the product of the divisors";
+    12 => "Hill Climbing Algorithm", [Search, Grid, Graph],
+        "Breadth-first search over the heightmap";
+    13 => "Distress Signal", [Parsing],
+        "Recursive packet parser with a total order, then sort";
+    14 => "Regolith Reservoir", [Simulation, Grid],
+        "Drop sand, resuming from the fall path of the previous unit";
+    15 => "Beacon Exclusion Zone", [Math],
+        "Merge row spans, rotate by 45 degrees to intersect sensor borders";
+    16 => "Proboscidea Volcanium", [Dp, Graph],
+        "DP over time, position and opened valves, split between two workers";
+    17 => "Pyroclastic Flow", [Simulation],
+        "Bitmask rows for the tower, skip ahead once the pattern repeats";
+    18 => "Boiling Boulders", [Search, Grid],
+        "Count open faces, flood fill the outside for the exterior";
+    19 => "Not Enough Minerals", [Search],
+        "Depth-first search over which robot to build next, with pruning";
+    20 => "Grove Positioning System", [Simulation],
+        "Mix the numbers by rotating a deque";
+    21 => "Monkey Math", [Graph, Parsing, Math],
+        "Evaluate the expression tree, invert operations on the path to humn";
+    22 => "Monkey Map", [Grid, Simulation],
+        "Walk the map, folding it into a cube to wrap around edges";
+    23 => "Unstable Diffusion", [Simulation, Grid],
+        "Cellular automaton over a set of elf positions";
+    24 => "Blizzard Basin", [Search, Grid],
+        "Breadth-first search on bitmask rows of blizzards and reachable cells";
+    25 => "Full of Hot Air", [Math],
+        "Balanced base 5 conversion";
+};
+
+/// Every puzzle, in order.
+#[must_use]
+pub fn puzzles() -> &'static [Puzzle] {
+    &PUZZLES
+}
+
+#[must_use]
+pub fn puzzle(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.get(usize::from(day).checked_sub(1)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_puzzle() {
+        assert_eq!(puzzle(7).unwrap().title, "No Space Left On Device");
+        assert_eq!(puzzle(25).unwrap().title, "Full of Hot Air");
+        assert!(puzzle(0).is_none());
+        assert!(puzzle(26).is_none());
+
+        let days = puzzles().iter().map(|p| p.day).collect::<Vec<_>>();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_tags() {
+        assert_eq!("dp".parse::<Tag>(), Ok(Tag::Dp));
+        assert!("dynamic".parse::<Tag>().is_err());
+        for tag in Tag::ALL {
+            assert_eq!(tag.to_string().parse::<Tag>(), Ok(tag));
+        }
+
+        let graph = puzzles()
+            .iter()
+            .filter(|p| p.has_tag(Tag::Graph))
+            .map(|p| p.day)
+            .collect::<Vec<_>>();
+        assert_eq!(graph, [7, 12, 16, 21]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Python bindings, built as an extension module named `aoc_2022`:
+//!
+//! ```text
+//! PYO3_BUILD_EXTENSION_MODULE=1 cargo rustc --lib --release --features python --crate-type cdylib
+//! cp target/release/libaoc_2022.so aoc_2022.so
+//! python -c 'import aoc_2022; print(aoc_2022.solve(1, 1, open("inputs/day01.txt").read()))'
+//! ```
+
+use crate::{day11, day7, registry, Part};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Solves one part of a day, raising `ValueError` if there is no answer.
+#[pyfunction]
+fn solve(day: u8, part: u8, input: &str) -> PyResult<String> {
+    let part = Part::try_from(part).map_err(PyValueError::new_err)?;
+    registry::solve(day, part, input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Total size of every directory by its absolute path, starting with `/`.
+#[pyfunction]
+fn day7_directory_sizes<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyDict>> {
+    let sizes = PyDict::new(py);
+    for (path, size) in day7::directory_sizes(input) {
+        sizes.set_item(path, size)?;
+    }
+    Ok(sizes)
+}
+
+#[pyclass(name = "Monkey", frozen, get_all)]
+struct PyMonkey {
+    items: Vec<u64>,
+    operation: String,
+    test_divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+#[pymethods]
+impl PyMonkey {
+    fn __repr__(&self) -> String {
+        format!(
+            "Monkey(items={:?}, operation='{}', test_divisor={}, if_true={}, if_false={})",
+            self.items, self.operation, self.test_divisor, self.if_true, self.if_false
+        )
+    }
+}
+
+/// The monkeys of a day 11 input, in order.
+#[pyfunction]
+fn day11_monkeys(input: &str) -> Vec<PyMonkey> {
+    day11::monkeys(input)
+        .iter()
+        .map(|monkey| {
+            let (if_true, if_false) = monkey.targets();
+            PyMonkey {
+                items: monkey.items().iter().copied().collect(),
+                operation: monkey.operation().to_string(),
+                test_divisor: monkey.test_divisor(),
+                if_true,
+                if_false,
+            }
+        })
+        .collect()
+}
+
+#[pymodule]
+fn aoc_2022(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(day7_directory_sizes, module)?)?;
+    module.add_function(wrap_pyfunction!(day11_monkeys, module)?)?;
+    module.add_class::<PyMonkey>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+    use std::ffi::CString;
+
+    fn run_python(code: &str) {
+        Python::initialize();
+        Python::attach(|py| {
+            let globals = PyDict::new(py);
+            globals
+                .set_item("aoc_2022", pyo3::wrap_pymodule!(aoc_2022)(py))
+                .unwrap();
+            let code = CString::new(code).unwrap();
+            if let Err(e) = py.run(&code, Some(&globals), None) {
+                panic!("{e}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_solve() {
+        run_python(indoc! {r#"
+            assert aoc_2022.solve(1, 1, "1000\n2000\n\n4000\n") == "4000"
+            for args in [(1, 3, ""), (26, 1, ""), (25, 2, "1\n")]:
+                try:
+                    aoc_2022.solve(*args)
+                except ValueError:
+                    pass
+                else:
+                    raise AssertionError(args)
+        "#});
+    }
+
+    #[test]
+    fn test_models() {
+        run_python(indoc! {r#"
+            sizes = aoc_2022.day7_directory_sizes("$ cd /\n$ ls\ndir a\n10 b\n$ cd a\n$ ls\n5 c\n")
+            assert sizes == {"/": 15, "/a": 5}, sizes
+
+            monkeys = aoc_2022.day11_monkeys(
+                "Monkey 0:\n"
+                "  Starting items: 79, 98\n"
+                "  Operation: new = old * 19\n"
+                "  Test: divisible by 23\n"
+                "    If true: throw to monkey 2\n"
+                "    If false: throw to monkey 3\n"
+            )
+            assert len(monkeys) == 1
+            assert monkeys[0].items == [79, 98]
+            assert monkeys[0].operation == "old * 19"
+            assert (monkeys[0].if_true, monkeys[0].if_false) == (2, 3)
+        "#});
+    }
+}
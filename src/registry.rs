@@ -0,0 +1,203 @@
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9, inputs, Part, Solution,
+};
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Object safe view of a [`Solution`], with the answers already rendered to strings.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn part1(&self, input: &str) -> String;
+    fn part2(&self, input: &str) -> Option<String>;
+}
+
+struct Registered<const DAY: u8, S>(S);
+
+impl<const DAY: u8, S> DynSolution for Registered<DAY, S>
+where
+    S: Solution<DAY> + Sync,
+{
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn part1(&self, input: &str) -> String {
+        self.0.part1(input).to_string()
+    }
+
+    fn part2(&self, input: &str) -> Option<String> {
+        self.0.part2(input).map(|result| result.to_string())
+    }
+}
+
+macro_rules! register {
+    ($($day:literal => $solution:expr),* $(,)?) => {
+        [$(&Registered::<$day, _>($solution) as &dyn DynSolution),*]
+    };
+}
+
+static SOLUTIONS: [&dyn DynSolution; 25] = register![
+    1 => day1::Day1,
+    2 => day2::Day2,
+    3 => day3::Day3,
+    4 => day4::Day4,
+    5 => day5::Day5,
+    6 => day6::Day6,
+    7 => day7::Day7,
+    8 => day8::Day8,
+    9 => day9::Day9,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15::puzzle(),
+    16 => day16::Day16,
+    17 => day17::Day17,
+    18 => day18::Day18,
+    19 => day19::Day19,
+    20 => day20::Day20,
+    21 => day21::Day21,
+    22 => day22::Day22,
+    23 => day23::Day23,
+    24 => day24::Day24,
+    25 => day25::Day25,
+];
+
+/// A named implementation of a day. The one in [`solutions`] is called [`DEFAULT_VARIANT`].
+#[derive(Copy, Clone)]
+pub struct Variant {
+    pub name: &'static str,
+    pub solution: &'static dyn DynSolution,
+}
+
+pub const DEFAULT_VARIANT: &str = "default";
+
+static ALTERNATIVES: [Variant; 1] = [Variant {
+    name: "linked-list",
+    solution: &Registered::<20, _>(day20::Day20LinkedList),
+}];
+
+/// Every solved day, in order.
+#[must_use]
+pub fn solutions() -> &'static [&'static dyn DynSolution] {
+    &SOLUTIONS
+}
+
+#[must_use]
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Every implementation of a day, starting with the default one.
+#[must_use]
+pub fn variants(day: u8) -> Vec<Variant> {
+    let default = solution(day).map(|solution| Variant {
+        name: DEFAULT_VARIANT,
+        solution,
+    });
+    default
+        .into_iter()
+        .chain(
+            ALTERNATIVES
+                .iter()
+                .filter(|variant| variant.solution.day() == day)
+                .copied(),
+        )
+        .collect()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    UnknownDay(u8),
+    NotSolved(u8, Part),
+    Panicked(u8, Part, String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "Could not find day {day}"),
+            SolveError::NotSolved(day, part) => write!(f, "Day {day:02} part {part} is not solved"),
+            SolveError::Panicked(day, part, message) => {
+                write!(f, "Day {day:02} part {part} failed: {message}")
+            }
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Solves one part of a day, turning unknown days, unsolved parts and panics into errors.
+///
+/// # Errors
+///
+/// Returns why there is no answer.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String, SolveError> {
+    let solution = solution(day).ok_or(SolveError::UnknownDay(day))?;
+    let input = &*inputs::normalise(input);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+        Part::One => Some(solution.part1(input)),
+        Part::Two => solution.part2(input),
+    }))
+    .map_err(|payload| SolveError::Panicked(day, part, panic_message(payload.as_ref())))?;
+    result.ok_or(SolveError::NotSolved(day, part))
+}
+
+/// The message passed to `panic!`, if it was a string.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_days_in_order() {
+        let days = solutions().iter().map(|s| s.day()).collect::<Vec<_>>();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_solution() {
+        let day = solution(6).unwrap();
+        assert_eq!(day.part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), "5");
+        assert_eq!(
+            day.part2("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Some("23".to_string())
+        );
+        assert!(solution(26).is_none());
+    }
+
+    #[test]
+    fn test_variants() {
+        let names = variants(20).iter().map(|v| v.name).collect::<Vec<_>>();
+        assert_eq!(names, vec![DEFAULT_VARIANT, "linked-list"]);
+        assert_eq!(variants(1).len(), 1);
+        assert!(variants(26).is_empty());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        assert_eq!(solve(6, Part::One, input), Ok("5".to_string()));
+        assert_eq!(solve(6, Part::Two, input), Ok("23".to_string()));
+        assert_eq!(
+            solve(25, Part::Two, "1="),
+            Err(SolveError::NotSolved(25, Part::Two))
+        );
+        assert_eq!(solve(26, Part::One, input), Err(SolveError::UnknownDay(26)));
+        assert!(matches!(
+            solve(16, Part::One, "not a valve"),
+            Err(SolveError::Panicked(16, Part::One, _))
+        ));
+    }
+}
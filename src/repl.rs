@@ -0,0 +1,580 @@
+//! A line-oriented shell for experimenting with one day's input without restarting.
+//!
+//! The parsed input of days 5, 15, 16 and 21 is kept between commands, so running a part again or
+//! querying the model doesn't parse anything. Other days are solved from the input text.
+
+use crate::day15::{Day15, Sensors};
+use crate::day16::FlowState;
+use crate::day21::MonkeyMap;
+use crate::day5::{self, CrateMover9000, CrateYard, Day5};
+use crate::registry::{self, SolveError};
+use crate::{inputs, Part, Solution};
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const HELP: &str = "\
+day <n>             Select a day and load its input from the inputs directory
+load <path>         Load another input for the selected day
+run [1|2]           Run both parts or a single one
+params              Show the parameters of the selected day
+set <name> <value>  Change a parameter, kept until another day is selected
+help                Show this help
+quit                Leave the shell";
+
+/// A parsed input with everything needed to answer both parts.
+trait Model {
+    fn part1(&self) -> String;
+    fn part2(&self) -> Option<String>;
+
+    /// Day specific commands as shown by `help`.
+    fn commands(&self) -> &'static str {
+        ""
+    }
+
+    /// Returns `None` if `command` is not one of [`Model::commands`].
+    fn query(&mut self, _command: &str, _args: &[&str]) -> Option<Result<String, String>> {
+        None
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("Unknown parameter {name}"))
+    }
+}
+
+struct Text {
+    day: u8,
+    input: String,
+}
+
+impl Model for Text {
+    fn part1(&self) -> String {
+        registry::solve(self.day, Part::One, &self.input).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn part2(&self) -> Option<String> {
+        match registry::solve(self.day, Part::Two, &self.input) {
+            Ok(answer) => Some(answer),
+            Err(SolveError::NotSolved(..)) => None,
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+struct Yard {
+    input: String,
+    yard: CrateYard,
+}
+
+impl Yard {
+    fn show(&self) -> String {
+        let last = self
+            .yard
+            .position()
+            .checked_sub(1)
+            .map_or(String::new(), |last| {
+                format!(", last {}", self.yard.moves()[last])
+            });
+        format!(
+            "After {} of {} moves{last}\n{}",
+            self.yard.position(),
+            self.yard.moves().len(),
+            self.yard.render()
+        )
+    }
+}
+
+impl Model for Yard {
+    // The parts are defined by the cranes of the puzzle, `set crane` only changes the yard
+    fn part1(&self) -> String {
+        format!("{} (crane 9000)", Day5.part1(&self.input))
+    }
+
+    fn part2(&self) -> Option<String> {
+        Day5.part2(&self.input)
+            .map(|answer| format!("{answer} (crane 9001)"))
+    }
+
+    fn commands(&self) -> &'static str {
+        "show                Draw the stacks after the moves applied so far\n\
+         undo                Take back the last move and draw the stacks\n\
+         redo                Apply the next move and draw the stacks\n\
+         seek <n>            Apply the first n moves and draw the stacks"
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let result = match (command, args) {
+            ("show", []) => Ok(()),
+            ("undo", []) => self
+                .yard
+                .undo()
+                .then_some(())
+                .ok_or_else(|| "No move to undo".to_string()),
+            ("redo", []) => match self.yard.redo() {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("No move left".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+            ("seek", [position]) => number(position)
+                .and_then(|position| self.yard.seek(position).map_err(|e| e.to_string())),
+            ("seek", _) => Err("Usage: seek <n>".to_string()),
+            ("show" | "undo" | "redo", _) => Err(format!("Usage: {command}")),
+            _ => return None,
+        };
+        Some(result.map(|()| self.show()))
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![("crane", self.yard.crane().name())]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        if name != "crane" {
+            return Err(format!("Unknown parameter {name}"));
+        }
+        let mut yard = CrateYard::parse(&self.input, day5::parse_crane(value)?)?;
+        // Stops before the failing move if the new crane can't get as far, redo tells why
+        let _ = yard.seek(self.yard.position());
+        self.yard = yard;
+        Ok(())
+    }
+}
+
+struct Beacons {
+    sensors: Sensors,
+    params: Day15,
+}
+
+impl Model for Beacons {
+    fn part1(&self) -> String {
+        self.sensors
+            .covered_in_row(self.params.target_row())
+            .to_string()
+    }
+
+    fn part2(&self) -> Option<String> {
+        self.sensors
+            .tuning_frequency(self.params.max_coordinate())
+            .map(|frequency| frequency.to_string())
+    }
+
+    fn commands(&self) -> &'static str {
+        "row <y>             Count the positions in row y that can't hold a beacon"
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let result = match (command, args) {
+            ("row", [row]) => number(row).map(|row| self.sensors.covered_in_row(row).to_string()),
+            ("row", _) => Err("Usage: row <y>".to_string()),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("target_row", self.params.target_row().to_string()),
+            ("max_coordinate", self.params.max_coordinate().to_string()),
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let value = number(value)?;
+        self.params = match name {
+            "target_row" => Day15::new(value, self.params.max_coordinate()),
+            "max_coordinate" => Day15::new(self.params.target_row(), value),
+            _ => return Err(format!("Unknown parameter {name}")),
+        };
+        Ok(())
+    }
+}
+
+impl Model for FlowState {
+    fn part1(&self) -> String {
+        self.max_release(30).unwrap().to_string()
+    }
+
+    fn part2(&self) -> Option<String> {
+        self.max_release_with_elephant(26)
+            .map(|flow| flow.to_string())
+    }
+
+    fn commands(&self) -> &'static str {
+        "release <minutes>   Most pressure released alone in at most 30 minutes\n\
+         elephant <minutes>  Most pressure released together with the elephant"
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let release = match command {
+            "release" => FlowState::max_release,
+            "elephant" => FlowState::max_release_with_elephant,
+            _ => return None,
+        };
+        let result = match args {
+            [minutes] => number(minutes).and_then(|minutes| {
+                release(self, minutes)
+                    .map(|flow| flow.to_string())
+                    .ok_or_else(|| "Minutes must be between 1 and 30".to_string())
+            }),
+            _ => Err(format!("Usage: {command} <minutes>")),
+        };
+        Some(result)
+    }
+}
+
+impl Model for MonkeyMap {
+    fn part1(&self) -> String {
+        self.value("root").unwrap().to_string()
+    }
+
+    fn part2(&self) -> Option<String> {
+        self.human_value().map(|value| value.to_string())
+    }
+
+    fn commands(&self) -> &'static str {
+        "yell <name>         Show the number a monkey yells"
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let result = match (command, args) {
+            ("yell", [name]) => self
+                .value(name)
+                .map(|value| value.to_string())
+                .ok_or_else(|| format!("Could not find monkey {name}")),
+            ("yell", _) => Err("Usage: yell <name>".to_string()),
+            _ => return None,
+        };
+        Some(result)
+    }
+}
+
+fn parse_model(day: u8, input: &str) -> Box<dyn Model> {
+    match day {
+        5 => Box::new(Yard {
+            input: input.to_string(),
+            yard: CrateYard::parse(input, Box::new(CrateMover9000))
+                .unwrap_or_else(|e| panic!("{e}")),
+        }),
+        15 => Box::new(Beacons {
+            sensors: Sensors::parse(input),
+            params: Day15::puzzle(),
+        }),
+        16 => Box::new(FlowState::parse(input)),
+        21 => Box::new(MonkeyMap::parse(input)),
+        _ => Box::new(Text {
+            day,
+            input: input.to_string(),
+        }),
+    }
+}
+
+fn number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{value} is not a number"))
+}
+
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+pub struct Repl {
+    inputs: PathBuf,
+    day: Option<u8>,
+    model: Option<Box<dyn Model>>,
+    /// Parameters changed with `set`, applied again when another input is loaded.
+    params: Vec<(String, String)>,
+}
+
+impl Repl {
+    #[must_use]
+    pub fn new(inputs: &Path) -> Self {
+        Self {
+            inputs: inputs.to_path_buf(),
+            day: None,
+            model: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Reads commands from `input` until it ends or `quit` is entered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or writing fails.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            match self.day {
+                Some(day) => write!(output, "day{day:02}> ")?,
+                None => write!(output, "> ")?,
+            }
+            output.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                return writeln!(output);
+            };
+            match self.eval(&line) {
+                Ok(Reply::Output(text)) if text.is_empty() => {}
+                Ok(Reply::Output(text)) => writeln!(output, "{text}")?,
+                Ok(Reply::Quit) => return Ok(()),
+                Err(message) => writeln!(output, "Error: {message}")?,
+            }
+        }
+    }
+
+    /// Runs a single command. Panics of the solutions are reported as errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing why the command failed.
+    pub fn eval(&mut self, line: &str) -> Result<Reply, String> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.dispatch(line)))
+            .map_err(|payload| format!("Panicked: {}", registry::panic_message(payload.as_ref())))?
+    }
+
+    fn dispatch(&mut self, line: &str) -> Result<Reply, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&command, args)) = words.split_first() else {
+            return Ok(Reply::Output(String::new()));
+        };
+
+        let output = match (command, args) {
+            ("quit" | "exit", _) => return Ok(Reply::Quit),
+            ("help", _) => self.help(),
+            ("day", [day]) => self.select(number(day)?)?,
+            ("day", _) => return Err("Usage: day <n>".to_string()),
+            ("load", [path]) => self.load(Path::new(path))?,
+            ("load", _) => return Err("Usage: load <path>".to_string()),
+            ("run", []) => [Part::One, Part::Two]
+                .into_iter()
+                .map(|part| self.run_part(part))
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n"),
+            ("run", [part]) => self.run_part(part.parse()?)?,
+            ("params", _) => self
+                .model()?
+                .params()
+                .iter()
+                .map(|(name, value)| format!("{name} = {value}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ("set", [name, value]) => {
+                self.model_mut()?.set(name, value)?;
+                self.params.retain(|(other, _)| other != name);
+                self.params
+                    .push(((*name).to_string(), (*value).to_string()));
+                String::new()
+            }
+            ("set", _) => return Err("Usage: set <name> <value>".to_string()),
+            _ => {
+                let start = Instant::now();
+                let answer = self
+                    .model_mut()?
+                    .query(command, args)
+                    .ok_or_else(|| format!("Unknown command {command}, try help"))??;
+                format!("{answer} ({:?})", start.elapsed())
+            }
+        };
+        Ok(Reply::Output(output))
+    }
+
+    fn help(&self) -> String {
+        match self.model.as_ref().map(|model| model.commands()) {
+            Some(commands) if !commands.is_empty() => format!("{HELP}\n{commands}"),
+            _ => HELP.to_string(),
+        }
+    }
+
+    fn select(&mut self, day: u8) -> Result<String, String> {
+        if registry::solution(day).is_none() {
+            return Err(format!("Could not find day {day}"));
+        }
+        self.day = Some(day);
+        self.model = None;
+        self.params.clear();
+        self.load(&inputs::path(&self.inputs, day))
+    }
+
+    fn load(&mut self, path: &Path) -> Result<String, String> {
+        let day = self.day.ok_or("Select a day first")?;
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let input = inputs::normalise(&input);
+
+        let start = Instant::now();
+        // Drop the old model first, in case parsing the new input panics
+        self.model = None;
+        let mut model = parse_model(day, &input);
+        for (name, value) in &self.params {
+            model.set(name, value)?;
+        }
+        self.model = Some(model);
+        Ok(format!(
+            "Loaded {} for day {day:02} in {:?}",
+            path.display(),
+            start.elapsed()
+        ))
+    }
+
+    fn run_part(&self, part: Part) -> Result<String, String> {
+        let model = self.model()?;
+        let start = Instant::now();
+        let answer = match part {
+            Part::One => model.part1(),
+            Part::Two => model.part2().unwrap_or_else(|| "Not solved".to_string()),
+        };
+        Ok(format!("Part {part}: {answer} ({:?})", start.elapsed()))
+    }
+
+    fn model(&self) -> Result<&dyn Model, String> {
+        self.model
+            .as_deref()
+            .ok_or_else(|| "Load an input first".to_string())
+    }
+
+    fn model_mut(&mut self) -> Result<&mut Box<dyn Model>, String> {
+        self.model
+            .as_mut()
+            .ok_or_else(|| "Load an input first".to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+    use std::fs;
+
+    const DAY15_INPUT: &str = indoc! {"
+        Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16
+        Sensor at x=13, y=2: closest beacon is at x=15, y=3
+        Sensor at x=12, y=14: closest beacon is at x=10, y=16
+        Sensor at x=10, y=20: closest beacon is at x=10, y=16
+        Sensor at x=14, y=17: closest beacon is at x=10, y=16
+        Sensor at x=8, y=7: closest beacon is at x=2, y=10
+        Sensor at x=2, y=0: closest beacon is at x=2, y=10
+        Sensor at x=0, y=11: closest beacon is at x=2, y=10
+        Sensor at x=20, y=14: closest beacon is at x=25, y=17
+        Sensor at x=17, y=20: closest beacon is at x=21, y=22
+        Sensor at x=16, y=7: closest beacon is at x=15, y=3
+        Sensor at x=14, y=3: closest beacon is at x=15, y=3
+        Sensor at x=20, y=1: closest beacon is at x=15, y=3
+    "};
+
+    fn repl_with_input(name: &str, day: u8, input: &str) -> Repl {
+        let dir = std::env::temp_dir().join(format!("aoc_2022-repl-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(inputs::path(&dir, day), input).unwrap();
+        let mut repl = Repl::new(&dir);
+        assert!(repl.eval(&format!("day {day}")).is_ok());
+        repl
+    }
+
+    fn output(repl: &mut Repl, line: &str) -> String {
+        match repl.eval(line) {
+            Ok(Reply::Output(text)) => text,
+            Ok(Reply::Quit) => panic!("{line} quit"),
+            Err(e) => panic!("{line} failed: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let mut repl = repl_with_input("params", 15, DAY15_INPUT);
+        output(&mut repl, "set target_row 10");
+        output(&mut repl, "set max_coordinate 20");
+        assert!(output(&mut repl, "run 1").starts_with("Part 1: 26 ("));
+        assert!(output(&mut repl, "run 2").starts_with("Part 2: 56000011 ("));
+        assert!(output(&mut repl, "row 10").starts_with("26 ("));
+        assert_eq!(
+            output(&mut repl, "params"),
+            "target_row = 10\nmax_coordinate = 20"
+        );
+
+        // Parameters survive loading the input again
+        let path = inputs::path(&repl.inputs, 15);
+        output(&mut repl, &format!("load {}", path.display()));
+        assert!(output(&mut repl, "run 1").starts_with("Part 1: 26 ("));
+    }
+
+    #[test]
+    fn test_text_model() {
+        let mut repl = repl_with_input("text", 1, "1000\n2000\n\n4000\n");
+        let answers = output(&mut repl, "run");
+        assert!(answers.starts_with("Part 1: 4000 ("));
+        assert!(answers.contains("\nPart 2: 7000 ("));
+
+        let mut repl = repl_with_input("text-failed", 6, "abcdefg\n");
+        assert!(output(&mut repl, "run 1").starts_with("Part 1: 4 ("));
+        assert!(repl
+            .eval("run 2")
+            .is_err_and(|e| e.starts_with("Panicked: Day 06 part 2 failed: ")));
+
+        let mut repl = repl_with_input("text-unsolved", 25, "1=\n");
+        assert!(output(&mut repl, "run 2").starts_with("Part 2: Not solved ("));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut repl = Repl::new(Path::new("does-not-exist"));
+        assert!(repl.eval("run").is_err());
+        assert!(repl.eval("day 26").is_err());
+        assert!(repl.eval("day 1").is_err());
+        assert!(repl.eval("frobnicate").is_err());
+        assert!(matches!(repl.eval("quit"), Ok(Reply::Quit)));
+
+        let mut repl = repl_with_input("errors", 21, "root: humn + humn\n");
+        assert!(repl.eval("yell nobody").is_err());
+        assert!(repl.eval("set target_row 1").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut repl = repl_with_input("run", 21, "root: pppw + humn\npppw: 4\nhumn: 5\n");
+        let mut out = Vec::new();
+        repl.run("yell pppw\nrun 2\nquit\n".as_bytes(), &mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("day21> 4 ("));
+        assert!(out.contains("day21> Part 2: 4 ("));
+    }
+
+    #[test]
+    fn test_crate_yard() {
+        let input = indoc! {"
+                [D]
+            [N] [C]
+            [Z] [M] [P]
+             1   2   3
+
+            move 1 from 2 to 1
+            move 3 from 1 to 3
+        "};
+        let mut repl = repl_with_input("yard", 5, input);
+        assert!(output(&mut repl, "show").starts_with("After 0 of 2 moves\n    [D]\n"));
+        assert!(output(&mut repl, "redo")
+            .starts_with("After 1 of 2 moves, last move 1 from 2 to 1\n[D]\n"));
+        assert!(output(&mut repl, "seek 2").contains("\n        [Z]\n"));
+        assert!(repl.eval("redo").is_err());
+
+        output(&mut repl, "set crane 9001");
+        assert!(output(&mut repl, "show").contains("\n        [D]\n"));
+        assert_eq!(output(&mut repl, "params"), "crane = 9001");
+        assert!(output(&mut repl, "run 1").starts_with("Part 1: CZ (crane 9000) ("));
+        assert!(output(&mut repl, "run 2").starts_with("Part 2: CD (crane 9001) ("));
+        assert!(repl.eval("set crane 9002").is_err());
+        output(&mut repl, "set crane capacity:1");
+        assert!(output(&mut repl, "show").starts_with("After 1 of 2 moves"));
+        assert_eq!(
+            repl.eval("redo").err().as_deref(),
+            Some("the crane lifts at most 1 crates, the move needs 3")
+        );
+    }
+}
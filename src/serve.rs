@@ -0,0 +1,106 @@
+//! A small HTTP/1.1 service around [`registry::solve`] for tools that can't link Rust.
+//!
+//! `POST /solve/{day}/{part}` takes the puzzle input as the body and answers with
+//! `{"day": 6, "part": 1, "answer": "5", "duration_ns": 1234}`. Failures are reported as
+//! `{"error": "..."}` with a 4xx status.
+
+use crate::{registry, Part};
+use serde_json::{json, Value};
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Instant;
+use tiny_http::{Header, Method, Request, Response};
+
+pub struct Server {
+    inner: tiny_http::Server,
+}
+
+impl Server {
+    /// Listens on `127.0.0.1:port`. Port 0 picks a free port, see [`Server::port`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port can't be bound.
+    pub fn bind(port: u16) -> io::Result<Server> {
+        let inner = tiny_http::Server::http(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+            .map_err(io::Error::other)?;
+        Ok(Server { inner })
+    }
+
+    #[must_use]
+    pub fn port(&self) -> u16 {
+        self.inner
+            .server_addr()
+            .to_ip()
+            .map_or(0, |address| address.port())
+    }
+
+    /// Answers requests one at a time until the listener is closed.
+    pub fn run(&self) {
+        for request in self.inner.incoming_requests() {
+            let _ = handle(request);
+        }
+    }
+}
+
+fn handle(mut request: Request) -> io::Result<()> {
+    let (status, body) = match route(request.method(), request.url()) {
+        Ok((day, part)) => {
+            let mut input = String::new();
+            match request.as_reader().read_to_string(&mut input) {
+                Ok(_) => solve(day, part, &input),
+                Err(_) => error(400, "Body must be UTF-8 text"),
+            }
+        }
+        Err(rejection) => rejection,
+    };
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    request.respond(
+        Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type),
+    )
+}
+
+fn route(method: &Method, url: &str) -> Result<(u8, Part), (u16, Value)> {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments = path.split('/').collect::<Vec<_>>();
+    let ["", "solve", day, part] = segments[..] else {
+        return Err(error(404, &format!("No route for {path}")));
+    };
+    if *method != Method::Post {
+        return Err(error(405, "Use POST with the puzzle input as the body"));
+    }
+
+    let day = day
+        .parse::<u8>()
+        .ok()
+        .filter(|&day| registry::solution(day).is_some())
+        .ok_or_else(|| error(404, &format!("Could not find day {day}")))?;
+    let part = part.parse::<Part>().map_err(|e| error(404, &e))?;
+    Ok((day, part))
+}
+
+fn solve(day: u8, part: Part, input: &str) -> (u16, Value) {
+    let start = Instant::now();
+    let result = registry::solve(day, part, input);
+    let duration = start.elapsed();
+
+    match result {
+        Ok(answer) => (
+            200,
+            json!({
+                "day": day,
+                "part": u8::from(part),
+                "answer": answer,
+                "duration_ns": u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+            }),
+        ),
+        Err(e) => error(422, &e.to_string()),
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
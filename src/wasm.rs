@@ -0,0 +1,28 @@
+//! JavaScript bindings for `wasm32-unknown-unknown`.
+//!
+//! Build with the `wasm` feature and without the default features, which need a network stack.
+//! Rayon is left out on wasm32 even if `parallel` is enabled, since the browser has no threads:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --crate-type cdylib \
+//!     --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/aoc_2022.wasm
+//! ```
+
+use crate::{registry, Part};
+use wasm_bindgen::prelude::*;
+
+/// Solves one part of a day, rejecting with a message if there is no answer.
+///
+/// # Errors
+///
+/// Returns a message if the day or part is unknown or unsolved.
+///
+/// # Panics
+///
+/// Panics if the day cannot solve the input. `wasm32-unknown-unknown` aborts on panic, so this
+/// traps instead of returning an error and the module has to be instantiated again.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    registry::solve(day, Part::try_from(part)?, input).map_err(|e| e.to_string())
+}
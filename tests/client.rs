@@ -0,0 +1,150 @@
+#![cfg(feature = "client")]
+
+use aoc_2022::client::{Client, ClientError, Fetched};
+use aoc_2022::ledger::Verdict;
+use aoc_2022::Part;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Answers every request with `status` and `body`, remembering the requests it saw.
+fn stub_server(status: u16, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(&stream);
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            head.push_str(&String::from_utf8(request_body).unwrap());
+            seen.lock().unwrap().push(head);
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+fn temp_inputs(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_2022-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_fetch_caches_input() {
+    let (url, requests) = stub_server(200, "1000\n2000\n");
+    let inputs = temp_inputs("fetch");
+    let client = Client::new(&url, "secret", &inputs).min_interval(Duration::ZERO);
+
+    let path = inputs.join("day01.txt");
+    assert_eq!(client.fetch(1).unwrap(), Fetched::Downloaded(path.clone()));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+    assert_eq!(client.fetch(1).unwrap(), Fetched::Cached(path));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /day/1/input HTTP/1.1"));
+    assert!(requests[0].contains("session=secret"));
+}
+
+#[test]
+fn test_fetch_failure_writes_nothing() {
+    let (url, _) = stub_server(404, "Not found");
+    let inputs = temp_inputs("failure");
+    let client = Client::new(&url, "secret", &inputs).min_interval(Duration::ZERO);
+
+    assert!(matches!(client.fetch(2), Err(ClientError::Status(404, _))));
+    assert!(!inputs.join("day02.txt").exists());
+}
+
+#[test]
+fn test_fetch_rate_limit() {
+    let (url, _) = stub_server(200, "input");
+    let inputs = temp_inputs("rate-limit");
+    let client = Client::new(&url, "secret", &inputs).min_interval(Duration::from_millis(300));
+
+    let start = Instant::now();
+    client.fetch(3).unwrap();
+    client.fetch(4).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[test]
+fn test_submit() {
+    let (url, requests) = stub_server(
+        200,
+        "<article><p>That's not the right answer; your answer is too high.</p></article>",
+    );
+    let inputs = temp_inputs("submit");
+    let client = Client::new(&url, "secret", &inputs).min_interval(Duration::ZERO);
+
+    assert_eq!(
+        client.submit(1, Part::Two, "45000").unwrap(),
+        Verdict::TooHigh
+    );
+
+    let requests = requests.lock().unwrap();
+    assert!(requests[0].starts_with("POST /day/1/answer HTTP/1.1"));
+    assert!(requests[0].contains("session=secret"));
+    assert!(requests[0].ends_with("level=2&answer=45000"));
+}
+
+#[test]
+fn test_submit_verdicts() {
+    let verdicts = [
+        (
+            "That's the right answer! You are one gold star closer.",
+            Verdict::Correct,
+        ),
+        (
+            "That's not the right answer; your answer is too low.",
+            Verdict::TooLow,
+        ),
+        ("That's not the right answer.", Verdict::Wrong),
+        (
+            "You gave an answer too recently; you have to wait.",
+            Verdict::Wait,
+        ),
+        (
+            "You don't seem to be solving the right level.",
+            Verdict::WrongLevel,
+        ),
+        ("<html>Please log in</html>", Verdict::Unknown),
+    ];
+    for (page, expected) in verdicts {
+        let (url, _) = stub_server(200, page);
+        let client =
+            Client::new(&url, "secret", &temp_inputs("verdicts")).min_interval(Duration::ZERO);
+        assert_eq!(
+            client.submit(2, Part::One, "15").unwrap(),
+            expected,
+            "{page}"
+        );
+    }
+}
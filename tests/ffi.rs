@@ -0,0 +1,59 @@
+#![cfg(feature = "ffi")]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_header_up_to_date() {
+    let generated = Path::new(env!("OUT_DIR")).join("aoc_2022.h");
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc_2022.h");
+    assert!(
+        fs::read_to_string(&generated).unwrap() == fs::read_to_string(&committed).unwrap(),
+        "{} is out of date, copy {} over it",
+        committed.display(),
+        generated.display()
+    );
+}
+
+/// Builds the static library, links `tests/ffi/solve.c` against it with `cc` and runs it.
+#[test]
+fn test_c_program() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(root)
+        .args([
+            "rustc",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            "ffi",
+        ])
+        .args(["--crate-type", "staticlib", "--target-dir"])
+        .arg(&target)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Building the static library failed");
+
+    let program = target.join("solve");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(root.join("tests/ffi/solve.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(target.join("debug/libaoc_2022.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Compiling tests/ffi/solve.c failed");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,33 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc_2022.h"
+
+static enum AocStatus solve(uint8_t day, uint8_t part, const char *input, char *out, size_t *out_len) {
+    return aoc_solve(day, part, (const uint8_t *)input, strlen(input), (uint8_t *)out, out_len);
+}
+
+int main(void) {
+    const char *input = "1000\n2000\n\n4000\n";
+    char out[32];
+    size_t out_len = sizeof out;
+
+    assert(solve(1, 1, input, out, &out_len) == AOC_STATUS_OK);
+    assert(out_len == 4 && memcmp(out, "4000", 4) == 0);
+
+    out_len = 2;
+    assert(solve(1, 2, input, out, &out_len) == AOC_STATUS_BUFFER_TOO_SMALL);
+    assert(out_len == 4);
+
+    out_len = sizeof out;
+    assert(solve(26, 1, input, out, &out_len) == AOC_STATUS_UNKNOWN_DAY);
+    assert(solve(1, 3, input, out, &out_len) == AOC_STATUS_UNKNOWN_PART);
+    assert(solve(25, 2, "1=\n", out, &out_len) == AOC_STATUS_NOT_SOLVED);
+    assert(solve(16, 1, "not a valve", out, &out_len) == AOC_STATUS_PANICKED);
+    assert(solve(1, 1, "\xff", out, &out_len) == AOC_STATUS_INVALID_UTF8);
+    assert(aoc_solve(1, 1, NULL, 3, (uint8_t *)out, &out_len) == AOC_STATUS_NULL_POINTER);
+
+    puts("ok");
+    return 0;
+}
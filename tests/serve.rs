@@ -0,0 +1,63 @@
+#![cfg(feature = "serve")]
+
+use aoc_2022::serve::Server;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::OnceLock;
+use std::thread;
+
+fn port() -> u16 {
+    static PORT: OnceLock<u16> = OnceLock::new();
+    *PORT.get_or_init(|| {
+        let server = Server::bind(0).unwrap();
+        let port = server.port();
+        thread::spawn(move || server.run());
+        port
+    })
+}
+
+fn request(method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port())).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+fn json(body: &str) -> serde_json::Value {
+    serde_json::from_str(body).unwrap()
+}
+
+#[test]
+fn test_solve() {
+    let (status, body) = request("POST", "/solve/6/2", "bvwbjplbgvbhsrlpgdmjqwftvncz");
+    assert_eq!(status, 200);
+    let body = json(&body);
+    assert_eq!(body["day"], 6);
+    assert_eq!(body["part"], 2);
+    assert_eq!(body["answer"], "23");
+    assert!(body["duration_ns"].is_u64());
+}
+
+#[test]
+fn test_unsolved_part() {
+    let (status, body) = request("POST", "/solve/25/2", "1=");
+    assert_eq!(status, 422);
+    assert!(json(&body)["error"].is_string());
+}
+
+#[test]
+fn test_unknown_routes() {
+    assert_eq!(request("POST", "/solve/26/1", "").0, 404);
+    assert_eq!(request("POST", "/solve/1/3", "").0, 404);
+    assert_eq!(request("POST", "/answers", "").0, 404);
+    assert_eq!(request("GET", "/solve/1/1", "").0, 405);
+}
@@ -0,0 +1,93 @@
+//! Runs every day with a puzzle input in `inputs/` and compares the answers against
+//! `tests/snapshots/dayNN.txt`.
+//!
+//! Puzzle inputs are personal, so neither they nor their snapshots are committed, and days
+//! without an input file (or with an empty one) are skipped and listed. Solving every day takes a
+//! long time in a debug build, so the test is ignored by default:
+//!
+//! ```text
+//! cargo test --release --test snapshots -- --ignored
+//! ```
+//!
+//! After an intentional change, re-accept the answers by running it with `UPDATE_SNAPSHOTS=1`.
+
+use aoc_2022::inputs;
+use aoc_2022::registry::{self, DynSolution};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn input_path(day: u8) -> PathBuf {
+    root().join("inputs").join(format!("day{day:02}.txt"))
+}
+
+fn snapshot_path(day: u8) -> PathBuf {
+    root()
+        .join("tests")
+        .join("snapshots")
+        .join(format!("day{day:02}.txt"))
+}
+
+fn render(solution: &dyn DynSolution, input: &str) -> String {
+    let part1 = solution.part1(input);
+    let part2 = solution
+        .part2(input)
+        .unwrap_or_else(|| "Not solved".to_string());
+    format!("Part 1:\n{part1}\nPart 2:\n{part2}\n")
+}
+
+#[test]
+#[ignore = "slow, run with `cargo test --release --test snapshots -- --ignored`"]
+fn test_real_inputs() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failures = Vec::new();
+    let mut skipped = Vec::new();
+
+    for &solution in registry::solutions() {
+        let day = solution.day();
+        let input = match fs::read_to_string(input_path(day)) {
+            Ok(input) if !input.trim().is_empty() => inputs::normalise(&input).into_owned(),
+            _ => {
+                skipped.push(format!("{day:02}"));
+                continue;
+            }
+        };
+        let actual = render(solution, &input);
+        let snapshot = snapshot_path(day);
+
+        if update {
+            fs::create_dir_all(snapshot.parent().unwrap()).unwrap();
+            fs::write(&snapshot, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) if inputs::normalise(&expected) == actual => {}
+            Ok(expected) => failures.push(format!(
+                "Day {day:02} differs from {}\n--- expected\n{expected}--- actual\n{actual}",
+                snapshot.display()
+            )),
+            Err(_) => failures.push(format!(
+                "Day {day:02} has no snapshot at {}\n--- actual\n{actual}",
+                snapshot.display()
+            )),
+        }
+    }
+
+    if !skipped.is_empty() {
+        eprintln!(
+            "Skipped days without an input in {}: {}",
+            root().join("inputs").display(),
+            skipped.join(", ")
+        );
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\nRun with UPDATE_SNAPSHOTS=1 to accept the new answers",
+        failures.join("\n")
+    );
+}